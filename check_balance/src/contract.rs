@@ -1,53 +1,230 @@
-use cosmwasm_std::{
-    entry_point, to_json_binary, Addr, BalanceResponse as BankBalanceResponse, BankQuery, Binary,
-    Coin, Deps, DepsMut, Env, MessageInfo, QueryRequest, Response, StdResult,
-};
-
-use crate::msg::{BalanceResponse, DeployerResponse, InstantiateMsg, QueryMsg};
-use crate::state::DEPLOYER;
-
-#[entry_point]
-pub fn instantiate(
-    deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
-    _msg: InstantiateMsg,
-) -> StdResult<Response> {
-    // Сохраняем адрес того, кто инициализировал контракт
-    let deployer = info.sender.clone();
-    DEPLOYER.save(deps.storage, &deployer)?;
-
-    Ok(Response::new()
-        .add_attribute("action", "instantiate")
-        .add_attribute("deployer", deployer))
-}
-
-#[entry_point]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::Balance { user } => {
-            let address: Addr = deps.api.addr_validate(&user)?;
-
-            let denom = "uaxm".to_string();
-
-            let balance = query_native_balance(deps, address.to_string(), denom)?;
-            let resp = BalanceResponse { balance };
-
-            to_json_binary(&resp)
-        }
-        QueryMsg::Deployer {} => {
-            let addr = DEPLOYER.load(deps.storage)?;
-            let resp = DeployerResponse {
-                deployer: addr.to_string(),
-            };
-            to_json_binary(&resp)
-        }
-    }
-}
-
-fn query_native_balance(deps: Deps, address: String, denom: String) -> StdResult<Coin> {
-    let res: BankBalanceResponse = deps
-        .querier
-        .query(&QueryRequest::Bank(BankQuery::Balance { address, denom }))?;
-    Ok(res.amount)
-}
+use cosmwasm_std::{
+    entry_point, to_json_binary, Addr, AllBalanceResponse, BalanceResponse as BankBalanceResponse,
+    BankQuery, Binary, Coin, Deps, DepsMut, Env, MessageInfo, QueryRequest, Response, StdError,
+    StdResult, Uint128,
+};
+use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20QueryMsg};
+
+use crate::msg::{
+    AllBalancesResponse, BalanceResponse, Cw20Balance, Cw20BalancesResponse, Cw20Position,
+    DeployerResponse, InstantiateMsg, PortfolioResponse, QueryMsg, StakingPosition,
+};
+use crate::staking::{ConfigResponse, RewardResponse, StakeResponse, StakingQueryMsg};
+use crate::state::DEPLOYER;
+
+const DEFAULT_DENOM: &str = "uaxm";
+// каждый токен - отдельный smart query, ограничиваем газ одного запроса
+const MAX_TOKENS: usize = 30;
+// на каждый staking контракт 3 запроса плюс до 2 токенов
+const MAX_STAKING_CONTRACTS: usize = 10;
+
+#[entry_point]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> StdResult<Response> {
+    // Сохраняем адрес того, кто инициализировал контракт
+    let deployer = info.sender.clone();
+    DEPLOYER.save(deps.storage, &deployer)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("deployer", deployer))
+}
+
+#[entry_point]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Balance { user, denom } => {
+            let address: Addr = deps.api.addr_validate(&user)?;
+
+            let denom = denom.unwrap_or_else(|| DEFAULT_DENOM.to_string());
+
+            let balance = query_native_balance(deps, address.to_string(), denom)?;
+            let resp = BalanceResponse { balance };
+
+            to_json_binary(&resp)
+        }
+        QueryMsg::AllBalances { user } => {
+            let address: Addr = deps.api.addr_validate(&user)?;
+
+            let balances = query_all_native_balances(deps, address.to_string())?;
+            let resp = AllBalancesResponse { balances };
+
+            to_json_binary(&resp)
+        }
+        QueryMsg::Cw20Balances { user, tokens } => {
+            let address: Addr = deps.api.addr_validate(&user)?;
+
+            let resp = query_cw20_balances(deps, &address, tokens)?;
+
+            to_json_binary(&resp)
+        }
+        QueryMsg::Portfolio {
+            user,
+            staking_contracts,
+        } => {
+            let address: Addr = deps.api.addr_validate(&user)?;
+
+            let resp = query_portfolio(deps, &address, staking_contracts)?;
+
+            to_json_binary(&resp)
+        }
+        QueryMsg::Deployer {} => {
+            let addr = DEPLOYER.load(deps.storage)?;
+            let resp = DeployerResponse {
+                deployer: addr.to_string(),
+            };
+            to_json_binary(&resp)
+        }
+    }
+}
+
+fn query_native_balance(deps: Deps, address: String, denom: String) -> StdResult<Coin> {
+    let res: BankBalanceResponse = deps
+        .querier
+        .query(&QueryRequest::Bank(BankQuery::Balance { address, denom }))?;
+    Ok(res.amount)
+}
+
+fn query_all_native_balances(deps: Deps, address: String) -> StdResult<Vec<Coin>> {
+    let res: AllBalanceResponse = deps
+        .querier
+        .query(&QueryRequest::Bank(BankQuery::AllBalances { address }))?;
+    Ok(res.amount)
+}
+
+fn query_cw20_balances(
+    deps: Deps,
+    address: &Addr,
+    tokens: Vec<String>,
+) -> StdResult<Cw20BalancesResponse> {
+    if tokens.len() > MAX_TOKENS {
+        return Err(StdError::generic_err(format!(
+            "Too many tokens, at most {} per query",
+            MAX_TOKENS
+        )));
+    }
+
+    let balances = tokens
+        .into_iter()
+        .map(|token| {
+            let token = deps.api.addr_validate(&token)?;
+            let balance = query_cw20_balance(deps, &token, address)?;
+            Ok(Cw20Balance {
+                token: token.to_string(),
+                balance,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(Cw20BalancesResponse { balances })
+}
+
+fn query_cw20_balance(deps: Deps, token: &Addr, address: &Addr) -> StdResult<Uint128> {
+    let res: Cw20BalanceResponse = deps.querier.query_wasm_smart(
+        token,
+        &Cw20QueryMsg::Balance {
+            address: address.to_string(),
+        },
+    )?;
+    Ok(res.balance)
+}
+
+fn query_portfolio(
+    deps: Deps,
+    address: &Addr,
+    staking_contracts: Vec<String>,
+) -> StdResult<PortfolioResponse> {
+    if staking_contracts.len() > MAX_STAKING_CONTRACTS {
+        return Err(StdError::generic_err(format!(
+            "Too many staking contracts, at most {} per query",
+            MAX_STAKING_CONTRACTS
+        )));
+    }
+
+    let native = query_all_native_balances(deps, address.to_string())?;
+
+    // Недоступный контракт не валит весь запрос, а попадает в error своей позиции
+    let mut tokens: Vec<String> = vec![];
+    let mut staking = Vec::with_capacity(staking_contracts.len());
+    for contract in staking_contracts {
+        let contract = deps.api.addr_validate(&contract)?;
+        match query_staking_position(deps, &contract, address) {
+            Ok((stake, reward, config)) => {
+                for token in std::iter::once(config.token_address).chain(config.receipt_token) {
+                    if !tokens.contains(&token) {
+                        tokens.push(token);
+                    }
+                }
+                staking.push(StakingPosition {
+                    contract: contract.to_string(),
+                    staked: Some(stake.amount),
+                    stake_time: Some(stake.stake_time),
+                    pending_reward: Some(reward.amount),
+                    error: None,
+                });
+            }
+            Err(err) => staking.push(StakingPosition {
+                contract: contract.to_string(),
+                staked: None,
+                stake_time: None,
+                pending_reward: None,
+                error: Some(err.to_string()),
+            }),
+        }
+    }
+
+    let cw20 = tokens
+        .into_iter()
+        .map(|token| {
+            let balance = deps
+                .api
+                .addr_validate(&token)
+                .and_then(|token| query_cw20_balance(deps, &token, address));
+            match balance {
+                Ok(balance) => Cw20Position {
+                    token,
+                    balance: Some(balance),
+                    error: None,
+                },
+                Err(err) => Cw20Position {
+                    token,
+                    balance: None,
+                    error: Some(err.to_string()),
+                },
+            }
+        })
+        .collect();
+
+    Ok(PortfolioResponse {
+        native,
+        cw20,
+        staking,
+    })
+}
+
+fn query_staking_position(
+    deps: Deps,
+    contract: &Addr,
+    address: &Addr,
+) -> StdResult<(StakeResponse, RewardResponse, ConfigResponse)> {
+    let stake: StakeResponse = deps.querier.query_wasm_smart(
+        contract,
+        &StakingQueryMsg::Stake {
+            address: address.to_string(),
+        },
+    )?;
+    let reward: RewardResponse = deps.querier.query_wasm_smart(
+        contract,
+        &StakingQueryMsg::Reward {
+            address: address.to_string(),
+        },
+    )?;
+    let config: ConfigResponse = deps
+        .querier
+        .query_wasm_smart(contract, &StakingQueryMsg::Config {})?;
+    Ok((stake, reward, config))
+}
@@ -1,5 +1,6 @@
-pub mod contract;
-pub mod msg;
-pub mod state;
-
-pub use crate::contract::query;
+pub mod contract;
+pub mod msg;
+pub mod staking;
+pub mod state;
+
+pub use crate::contract::query;
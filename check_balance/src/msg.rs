@@ -1,26 +1,85 @@
-use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Coin;
-
-#[cw_serde]
-pub struct InstantiateMsg {}
-
-#[cw_serde]
-#[derive(QueryResponses)]
-pub enum QueryMsg {
-    #[returns(BalanceResponse)]
-    Balance { user: String },
-
-    /// Вернуть адрес деплоера контракта
-    #[returns(DeployerResponse)]
-    Deployer {},
-}
-
-#[cw_serde]
-pub struct BalanceResponse {
-    pub balance: Coin,
-}
-
-#[cw_serde]
-pub struct DeployerResponse {
-    pub deployer: String,
-}
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Coin, Uint128};
+
+#[cw_serde]
+pub struct InstantiateMsg {}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Баланс в одном деноме, по умолчанию uaxm
+    #[returns(BalanceResponse)]
+    Balance { user: String, denom: Option<String> },
+
+    /// Все нативные балансы пользователя
+    #[returns(AllBalancesResponse)]
+    AllBalances { user: String },
+
+    /// Балансы пользователя в перечисленных cw20 контрактах, не больше 30 за раз
+    #[returns(Cw20BalancesResponse)]
+    Cw20Balances { user: String, tokens: Vec<String> },
+
+    /// Нативные и cw20 балансы плюс позиции в staking контрактах, не больше 10 за раз.
+    /// cw20 токены берутся из конфигов staking контрактов (стейк-токен и receipt).
+    #[returns(PortfolioResponse)]
+    Portfolio {
+        user: String,
+        staking_contracts: Vec<String>,
+    },
+
+    /// Вернуть адрес деплоера контракта
+    #[returns(DeployerResponse)]
+    Deployer {},
+}
+
+#[cw_serde]
+pub struct BalanceResponse {
+    pub balance: Coin,
+}
+
+#[cw_serde]
+pub struct AllBalancesResponse {
+    pub balances: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct Cw20Balance {
+    pub token: String,
+    pub balance: Uint128,
+}
+
+#[cw_serde]
+pub struct Cw20BalancesResponse {
+    /// В том же порядке, что и `tokens` в запросе
+    pub balances: Vec<Cw20Balance>,
+}
+
+#[cw_serde]
+pub struct Cw20Position {
+    pub token: String,
+    pub balance: Option<Uint128>,
+    /// Ошибка запроса к токену, если он недоступен
+    pub error: Option<String>,
+}
+
+#[cw_serde]
+pub struct StakingPosition {
+    pub contract: String,
+    pub staked: Option<Uint128>,
+    pub stake_time: Option<u64>,
+    pub pending_reward: Option<Uint128>,
+    /// Ошибка запроса к контракту, если он недоступен
+    pub error: Option<String>,
+}
+
+#[cw_serde]
+pub struct PortfolioResponse {
+    pub native: Vec<Coin>,
+    pub cw20: Vec<Cw20Position>,
+    pub staking: Vec<StakingPosition>,
+}
+
+#[cw_serde]
+pub struct DeployerResponse {
+    pub deployer: String,
+}
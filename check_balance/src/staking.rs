@@ -0,0 +1,33 @@
+//! Запросы к staking контракту из staking_protocol.
+//!
+//! Сам крейт подключить нельзя (он на cosmwasm 2.x), поэтому здесь копии
+//! нужных сообщений. Ответы разбираются без deny_unknown_fields, чтобы новые
+//! поля в staking не ломали портфель.
+
+use cosmwasm_std::Uint128;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StakingQueryMsg {
+    Stake { address: String },
+    Reward { address: String },
+    Config {},
+}
+
+#[derive(Deserialize)]
+pub struct StakeResponse {
+    pub amount: Uint128,
+    pub stake_time: u64,
+}
+
+#[derive(Deserialize)]
+pub struct RewardResponse {
+    pub amount: Uint128,
+}
+
+#[derive(Deserialize)]
+pub struct ConfigResponse {
+    pub token_address: String,
+    pub receipt_token: Option<String>,
+}
@@ -0,0 +1,334 @@
+use check_balance::contract::{instantiate, query};
+use check_balance::msg::{
+    AllBalancesResponse, BalanceResponse, Cw20BalancesResponse, InstantiateMsg, PortfolioResponse,
+    QueryMsg,
+};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    coin, to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError,
+    StdResult, Uint128,
+};
+use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20QueryMsg};
+use cw_multi_test::{App, AppBuilder, ContractWrapper, Executor};
+use cw_storage_plus::{Item, Map};
+
+// у check_balance нет execute, для ContractWrapper нужна заглушка
+fn no_execute(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+    Err(StdError::generic_err("no execute"))
+}
+
+// Минимальный cw20: балансы задаются при инициализации
+const MOCK_BALANCES: Map<&str, Uint128> = Map::new("balances");
+
+fn mock_cw20_instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: Vec<(String, Uint128)>,
+) -> StdResult<Response> {
+    for (addr, amount) in msg {
+        MOCK_BALANCES.save(deps.storage, &addr, &amount)?;
+    }
+    Ok(Response::new())
+}
+
+fn mock_cw20_query(deps: Deps, _env: Env, msg: Cw20QueryMsg) -> StdResult<Binary> {
+    match msg {
+        Cw20QueryMsg::Balance { address } => {
+            let balance = MOCK_BALANCES
+                .may_load(deps.storage, &address)?
+                .unwrap_or_default();
+            to_json_binary(&Cw20BalanceResponse { balance })
+        }
+        _ => Err(StdError::generic_err("unsupported")),
+    }
+}
+
+// Минимальный staking: один стейк на всех, конфиг с лишними полями как в настоящем
+#[cw_serde]
+struct MockStakingConfig {
+    token_address: String,
+    receipt_token: Option<String>,
+    apr: u64,
+}
+
+#[cw_serde]
+enum MockStakingQueryMsg {
+    Stake { address: String },
+    Reward { address: String },
+    Config {},
+}
+
+#[cw_serde]
+struct MockStakeResponse {
+    amount: Uint128,
+    stake_time: u64,
+}
+
+#[cw_serde]
+struct MockRewardResponse {
+    amount: Uint128,
+}
+
+const MOCK_CONFIG: Item<MockStakingConfig> = Item::new("config");
+
+fn mock_staking_instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: MockStakingConfig,
+) -> StdResult<Response> {
+    MOCK_CONFIG.save(deps.storage, &msg)?;
+    Ok(Response::new())
+}
+
+fn mock_staking_query(deps: Deps, _env: Env, msg: MockStakingQueryMsg) -> StdResult<Binary> {
+    match msg {
+        MockStakingQueryMsg::Stake { .. } => to_json_binary(&MockStakeResponse {
+            amount: Uint128::new(500),
+            stake_time: 1_000,
+        }),
+        MockStakingQueryMsg::Reward { .. } => to_json_binary(&MockRewardResponse {
+            amount: Uint128::new(7),
+        }),
+        MockStakingQueryMsg::Config {} => to_json_binary(&MOCK_CONFIG.load(deps.storage)?),
+    }
+}
+
+struct TestSetup {
+    app: App,
+    contract: Addr,
+    user: Addr,
+}
+
+impl TestSetup {
+    fn new() -> Self {
+        let user = Addr::unchecked("user");
+        let mut app = AppBuilder::new().build(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &user, vec![coin(1_000, "uaxm"), coin(50, "uatom")])
+                .unwrap();
+        });
+
+        let code_id = app.store_code(Box::new(ContractWrapper::new(
+            no_execute,
+            instantiate,
+            query,
+        )));
+        let contract = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("deployer"),
+                &InstantiateMsg {},
+                &[],
+                "check_balance",
+                None,
+            )
+            .unwrap();
+
+        TestSetup {
+            app,
+            contract,
+            user,
+        }
+    }
+
+    fn mock_cw20(&mut self, balances: Vec<(String, Uint128)>) -> Addr {
+        let code_id = self.app.store_code(Box::new(ContractWrapper::new(
+            no_execute,
+            mock_cw20_instantiate,
+            mock_cw20_query,
+        )));
+        self.app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("deployer"),
+                &balances,
+                &[],
+                "mock_cw20",
+                None,
+            )
+            .unwrap()
+    }
+
+    fn mock_staking(&mut self, token_address: &str, receipt_token: Option<&str>) -> Addr {
+        let code_id = self.app.store_code(Box::new(ContractWrapper::new(
+            no_execute,
+            mock_staking_instantiate,
+            mock_staking_query,
+        )));
+        self.app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("deployer"),
+                &MockStakingConfig {
+                    token_address: token_address.to_string(),
+                    receipt_token: receipt_token.map(str::to_string),
+                    apr: 10,
+                },
+                &[],
+                "mock_staking",
+                None,
+            )
+            .unwrap()
+    }
+}
+
+#[test]
+fn native_balances() {
+    let setup = TestSetup::new();
+
+    let resp: BalanceResponse = setup
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &setup.contract,
+            &QueryMsg::Balance {
+                user: setup.user.to_string(),
+                denom: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(resp.balance, coin(1_000, "uaxm"));
+
+    let resp: BalanceResponse = setup
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &setup.contract,
+            &QueryMsg::Balance {
+                user: setup.user.to_string(),
+                denom: Some("uatom".to_string()),
+            },
+        )
+        .unwrap();
+    assert_eq!(resp.balance, coin(50, "uatom"));
+
+    let resp: AllBalancesResponse = setup
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &setup.contract,
+            &QueryMsg::AllBalances {
+                user: setup.user.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(resp.balances, vec![coin(50, "uatom"), coin(1_000, "uaxm")]);
+}
+
+#[test]
+fn cw20_balances_in_request_order() {
+    let mut setup = TestSetup::new();
+    let user = setup.user.to_string();
+    let token_a = setup.mock_cw20(vec![(user.clone(), Uint128::new(300))]);
+    let token_b = setup.mock_cw20(vec![]);
+
+    let resp: Cw20BalancesResponse = setup
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &setup.contract,
+            &QueryMsg::Cw20Balances {
+                user,
+                tokens: vec![token_b.to_string(), token_a.to_string()],
+            },
+        )
+        .unwrap();
+
+    assert_eq!(resp.balances.len(), 2);
+    assert_eq!(resp.balances[0].token, token_b.to_string());
+    assert_eq!(resp.balances[0].balance, Uint128::zero());
+    assert_eq!(resp.balances[1].token, token_a.to_string());
+    assert_eq!(resp.balances[1].balance, Uint128::new(300));
+}
+
+#[test]
+fn cw20_balances_token_limit() {
+    let mut setup = TestSetup::new();
+    let token = setup.mock_cw20(vec![]);
+
+    let err = setup
+        .app
+        .wrap()
+        .query_wasm_smart::<Cw20BalancesResponse>(
+            &setup.contract,
+            &QueryMsg::Cw20Balances {
+                user: setup.user.to_string(),
+                tokens: vec![token.to_string(); 31],
+            },
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains("Too many tokens"));
+}
+
+#[test]
+fn portfolio_combines_sources() {
+    let mut setup = TestSetup::new();
+    let user = setup.user.to_string();
+    let stake_token = setup.mock_cw20(vec![(user.clone(), Uint128::new(300))]);
+    let receipt_token = setup.mock_cw20(vec![(user.clone(), Uint128::new(40))]);
+    // оба пула на одном токене, баланс должен попасть в ответ один раз
+    let pool_a = setup.mock_staking(stake_token.as_str(), Some(receipt_token.as_str()));
+    let pool_b = setup.mock_staking(stake_token.as_str(), None);
+
+    let resp: PortfolioResponse = setup
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &setup.contract,
+            &QueryMsg::Portfolio {
+                user,
+                staking_contracts: vec![pool_a.to_string(), pool_b.to_string()],
+            },
+        )
+        .unwrap();
+
+    assert_eq!(resp.native, vec![coin(50, "uatom"), coin(1_000, "uaxm")]);
+
+    assert_eq!(resp.cw20.len(), 2);
+    assert_eq!(resp.cw20[0].token, stake_token.to_string());
+    assert_eq!(resp.cw20[0].balance, Some(Uint128::new(300)));
+    assert_eq!(resp.cw20[1].token, receipt_token.to_string());
+    assert_eq!(resp.cw20[1].balance, Some(Uint128::new(40)));
+
+    assert_eq!(resp.staking.len(), 2);
+    for (position, pool) in resp.staking.iter().zip([&pool_a, &pool_b]) {
+        assert_eq!(position.contract, pool.to_string());
+        assert_eq!(position.staked, Some(Uint128::new(500)));
+        assert_eq!(position.stake_time, Some(1_000));
+        assert_eq!(position.pending_reward, Some(Uint128::new(7)));
+        assert_eq!(position.error, None);
+    }
+}
+
+#[test]
+fn portfolio_reports_unreachable_contracts() {
+    let mut setup = TestSetup::new();
+    // токен пула указывает на адрес без контракта
+    let pool = setup.mock_staking("missing_token", None);
+
+    let resp: PortfolioResponse = setup
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &setup.contract,
+            &QueryMsg::Portfolio {
+                user: setup.user.to_string(),
+                staking_contracts: vec!["missing_pool".to_string(), pool.to_string()],
+            },
+        )
+        .unwrap();
+
+    assert_eq!(resp.staking[0].contract, "missing_pool");
+    assert_eq!(resp.staking[0].staked, None);
+    assert!(resp.staking[0].error.is_some());
+    assert_eq!(resp.staking[1].staked, Some(Uint128::new(500)));
+    assert_eq!(resp.staking[1].error, None);
+
+    assert_eq!(resp.cw20.len(), 1);
+    assert_eq!(resp.cw20[0].token, "missing_token");
+    assert_eq!(resp.cw20[0].balance, None);
+    assert!(resp.cw20[0].error.is_some());
+}
@@ -1,6 +0,0 @@
-pub mod contract;
-pub mod errors;
-pub mod msg;
-pub mod state;
-
-pub use crate::errors::ContractError;
\ No newline at end of file
@@ -1,10 +1,10 @@
-use cw_storage_plus::Item;
-
-pub const COUNT: Item<i32> = Item::new("count");
-
-// #[cw_serde]
-// pub struct Config {
-//     pub owner: Addr,
-// }
-
+use cw_storage_plus::Item;
+
+pub const COUNT: Item<i32> = Item::new("count");
+
+// #[cw_serde]
+// pub struct Config {
+//     pub owner: Addr,
+// }
+
 // pub const CONFIG: Item<Config> = Item::new("config");
\ No newline at end of file
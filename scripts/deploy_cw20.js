@@ -16,10 +16,9 @@ const {
 const RPC_ENDPOINT = "http://api-docs.axiomeinfo.org:26657";
 const BECH32_PREFIX = "axm";
 
-// Пробуем сначала standart_cw20, потом cw20
+// Сообщение инстанцирования в формате cw20-base, его принимает cw20-token
 const WASM_PATHS = [
-  path.join(__dirname, "..", "standart_cw20", "artifacts", "standart_cw20.wasm"),
-  path.join(__dirname, "..", "cw20", "artifacts", "cw20.wasm"),
+  path.join(__dirname, "..", "staking_protocol", "contracts", "cw20-token", "artifacts", "cw20_token.wasm"),
 ];
 
 const encoder = new TextEncoder();
@@ -0,0 +1,282 @@
+use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, entry_point, to_json_binary, StdResult, WasmMsg, Uint128, Addr};
+use cw_ownable::initialize_owner;
+use cw_utils::Expiration;
+use sha2::{Digest, Sha256};
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, IsClaimedResponse, LatestStageResponse,
+    PayoutMode, QueryMsg, StageResponse,
+};
+use crate::state::{Config, Stage, CLAIMS, CONFIG, LATEST_STAGE, STAGES};
+
+#[entry_point]
+pub fn instantiate (
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    initialize_owner(deps.storage, deps.api, Some(&msg.owner))?;
+
+    let token_address = deps.api.addr_validate(&msg.token_address)?;
+    CONFIG.save(deps.storage, &Config { token_address: token_address.clone() })?;
+    LATEST_STAGE.save(deps.storage, &0)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("token_address", token_address))
+}
+
+#[entry_point]
+pub fn execute (
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::RegisterMerkleRoot { merkle_root, total_amount, expiration, payout } => {
+            execute_register_merkle_root(deps, env, info, merkle_root, total_amount, expiration, payout)
+        }
+        ExecuteMsg::Claim { stage, amount, proof } => {
+            execute_claim(deps, env, info, stage, amount, proof)
+        }
+        ExecuteMsg::Clawback { stage, recipient } => {
+            execute_clawback(deps, env, info, stage, recipient)
+        }
+        ExecuteMsg::UpdateOwnership(action) => {
+            cw_ownable::update_ownership(deps, &env.block, &info.sender, action)?;
+            Ok(Response::new().add_attribute("action", "update_ownership"))
+        }
+    }
+}
+
+#[entry_point]
+pub fn query (
+    deps: Deps,
+    _env: Env,
+    msg: QueryMsg,
+) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
+        QueryMsg::Stage {stage} => to_json_binary(&query_stage(deps, stage)?),
+        QueryMsg::LatestStage {} => {
+            to_json_binary(&LatestStageResponse {
+                latest_stage: LATEST_STAGE.load(deps.storage)?,
+            })
+        }
+        QueryMsg::IsClaimed {stage, address} => {
+            to_json_binary(&query_is_claimed(deps, stage, address)?)
+        }
+        QueryMsg::Ownership {} => {
+            to_json_binary(&cw_ownable::get_ownership(deps.storage)?)
+        }
+    }
+}
+
+pub fn execute_register_merkle_root(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    merkle_root: String,
+    total_amount: Uint128,
+    expiration: Option<Expiration>,
+    payout: PayoutMode,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let mut root = [0u8; 32];
+    hex::decode_to_slice(&merkle_root, &mut root)
+        .map_err(|_| ContractError::InvalidMerkleRoot {})?;
+
+    let expiration = expiration.unwrap_or_default();
+    if expiration.is_expired(&env.block) {
+        return Err(ContractError::InvalidExpiration {});
+    }
+
+    let stage = LATEST_STAGE.update(deps.storage, |stage| -> StdResult<_> {
+        Ok(stage + 1)
+    })?;
+
+    STAGES.save(deps.storage, stage, &Stage {
+        merkle_root: merkle_root.to_lowercase(),
+        total_amount,
+        claimed_amount: Uint128::zero(),
+        expiration,
+        payout,
+        closed: false,
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "register_merkle_root")
+        .add_attribute("stage", stage.to_string())
+        .add_attribute("merkle_root", merkle_root)
+        .add_attribute("total_amount", total_amount))
+}
+
+pub fn execute_claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    stage: u64,
+    amount: Uint128,
+    proof: Vec<String>,
+) -> Result<Response, ContractError> {
+    let mut stage_info = STAGES.load(deps.storage, stage)?;
+    if stage_info.closed {
+        return Err(ContractError::StageClosed { stage });
+    }
+    if stage_info.expiration.is_expired(&env.block) {
+        return Err(ContractError::StageExpired { stage });
+    }
+    if CLAIMS.has(deps.storage, (stage, &info.sender)) {
+        return Err(ContractError::AlreadyClaimed {});
+    }
+
+    verify_proof(&stage_info.merkle_root, &info.sender, amount, &proof)?;
+
+    stage_info.claimed_amount += amount;
+    if stage_info.claimed_amount > stage_info.total_amount {
+        return Err(ContractError::StageTotalExceeded {});
+    }
+    STAGES.save(deps.storage, stage, &stage_info)?;
+    CLAIMS.save(deps.storage, (stage, &info.sender), &amount)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let msg = payout_msg(&config.token_address, &stage_info.payout, &info.sender, amount)?;
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "claim")
+        .add_attribute("stage", stage.to_string())
+        .add_attribute("address", info.sender)
+        .add_attribute("amount", amount))
+}
+
+pub fn execute_clawback(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    stage: u64,
+    recipient: Option<String>,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let mut stage_info = STAGES.load(deps.storage, stage)?;
+    if stage_info.closed {
+        return Err(ContractError::StageClosed { stage });
+    }
+    if !stage_info.expiration.is_expired(&env.block) {
+        return Err(ContractError::StageNotExpired { stage });
+    }
+
+    stage_info.closed = true;
+    STAGES.save(deps.storage, stage, &stage_info)?;
+
+    let recipient = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => info.sender,
+    };
+    let remainder = stage_info.total_amount - stage_info.claimed_amount;
+
+    let mut response = Response::new();
+    // nothing is held for a mint stage, closing it is enough
+    if stage_info.payout == PayoutMode::Transfer && !remainder.is_zero() {
+        let config = CONFIG.load(deps.storage)?;
+        response = response.add_message(
+            payout_msg(&config.token_address, &PayoutMode::Transfer, &recipient, remainder)?,
+        );
+    }
+
+    Ok(response
+        .add_attribute("action", "clawback")
+        .add_attribute("stage", stage.to_string())
+        .add_attribute("recipient", recipient)
+        .add_attribute("amount", remainder))
+}
+
+fn verify_proof(
+    merkle_root: &str,
+    address: &Addr,
+    amount: Uint128,
+    proof: &[String],
+) -> Result<(), ContractError> {
+    let leaf: [u8; 32] = Sha256::digest(format!("{}{}", address, amount).as_bytes()).into();
+
+    let hash = proof.iter().try_fold(leaf, |hash, p| {
+        let mut sibling = [0u8; 32];
+        hex::decode_to_slice(p, &mut sibling)
+            .map_err(|_| ContractError::InvalidProof {})?;
+
+        let (first, second) = if hash <= sibling { (hash, sibling) } else { (sibling, hash) };
+        let mut hasher = Sha256::new();
+        hasher.update(first);
+        hasher.update(second);
+        Ok::<_, ContractError>(hasher.finalize().into())
+    })?;
+
+    if hex::encode(hash) != merkle_root {
+        return Err(ContractError::VerificationFailed {});
+    }
+    Ok(())
+}
+
+fn payout_msg(
+    token: &Addr,
+    payout: &PayoutMode,
+    recipient: &Addr,
+    amount: Uint128,
+) -> StdResult<WasmMsg> {
+    let msg = match payout {
+        PayoutMode::Transfer => cw20_token::msg::ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount,
+        },
+        PayoutMode::Mint => cw20_token::msg::ExecuteMsg::Mint {
+            recipient: recipient.to_string(),
+            amount,
+        },
+    };
+
+    Ok(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_json_binary(&msg)?,
+        funds: vec![],
+    })
+}
+
+fn query_config(
+    deps: Deps,
+) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        token_address: config.token_address.to_string(),
+    })
+}
+
+fn query_stage(
+    deps: Deps,
+    stage: u64,
+) -> StdResult<StageResponse> {
+    let info = STAGES.load(deps.storage, stage)?;
+    Ok(StageResponse {
+        stage,
+        merkle_root: info.merkle_root,
+        total_amount: info.total_amount,
+        claimed_amount: info.claimed_amount,
+        expiration: info.expiration,
+        payout: info.payout,
+        closed: info.closed,
+    })
+}
+
+fn query_is_claimed(
+    deps: Deps,
+    stage: u64,
+    address: String,
+) -> StdResult<IsClaimedResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    Ok(IsClaimedResponse {
+        is_claimed: CLAIMS.has(deps.storage, (stage, &addr)),
+    })
+}
\ No newline at end of file
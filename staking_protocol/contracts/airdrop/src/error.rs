@@ -0,0 +1,39 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+use cw_ownable::OwnershipError;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Ownership(#[from] OwnershipError),
+
+    #[error("Invalid merkle root, expected a hex encoded sha256 hash")]
+    InvalidMerkleRoot {},
+
+    #[error("Invalid proof entry, expected a hex encoded sha256 hash")]
+    InvalidProof {},
+
+    #[error("Merkle proof verification failed")]
+    VerificationFailed {},
+
+    #[error("Already claimed")]
+    AlreadyClaimed {},
+
+    #[error("Stage {stage} has expired")]
+    StageExpired { stage: u64 },
+
+    #[error("Stage {stage} has not expired yet")]
+    StageNotExpired { stage: u64 },
+
+    #[error("Stage {stage} is closed")]
+    StageClosed { stage: u64 },
+
+    #[error("Claim exceeds the stage total")]
+    StageTotalExceeded {},
+
+    #[error("Stage expiration is already in the past")]
+    InvalidExpiration {},
+}
\ No newline at end of file
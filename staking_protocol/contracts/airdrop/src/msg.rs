@@ -0,0 +1,90 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Uint128;
+use cw_ownable::Action;
+use cw_utils::Expiration;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub owner: String,
+    pub token_address: String,
+}
+
+#[cw_serde]
+pub enum PayoutMode {
+    /// Claims are paid from the contract's token balance, which has to
+    /// be funded with the stage total
+    Transfer,
+    /// Claims are minted, the contract has to be a minter of the token
+    Mint,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Opens a new stage, leaves are sha256(address + amount) and
+    /// sibling pairs are hashed in sorted order
+    RegisterMerkleRoot {
+        /// Hex encoded sha256 root
+        merkle_root: String,
+        total_amount: Uint128,
+        expiration: Option<Expiration>,
+        payout: PayoutMode,
+    },
+    Claim {
+        stage: u64,
+        amount: Uint128,
+        /// Hex encoded sibling hashes from the leaf up
+        proof: Vec<String>,
+    },
+    /// Closes an expired stage and returns what is left of a transfer
+    /// stage to the recipient, the owner by default
+    Clawback {
+        stage: u64,
+        recipient: Option<String>,
+    },
+    UpdateOwnership(Action),
+}
+
+#[derive(QueryResponses)]
+#[cw_serde]
+pub enum QueryMsg {
+    #[returns(ConfigResponse)]
+    Config {},
+
+    #[returns(StageResponse)]
+    Stage {stage: u64},
+
+    #[returns(LatestStageResponse)]
+    LatestStage {},
+
+    #[returns(IsClaimedResponse)]
+    IsClaimed {stage: u64, address: String},
+
+    #[returns(cw_ownable::Ownership<cosmwasm_std::Addr>)]
+    Ownership {},
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub token_address: String,
+}
+
+#[cw_serde]
+pub struct StageResponse {
+    pub stage: u64,
+    pub merkle_root: String,
+    pub total_amount: Uint128,
+    pub claimed_amount: Uint128,
+    pub expiration: Expiration,
+    pub payout: PayoutMode,
+    pub closed: bool,
+}
+
+#[cw_serde]
+pub struct LatestStageResponse {
+    pub latest_stage: u64,
+}
+
+#[cw_serde]
+pub struct IsClaimedResponse {
+    pub is_claimed: bool,
+}
\ No newline at end of file
@@ -0,0 +1,27 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map};
+use cosmwasm_schema::cw_serde;
+use cw_utils::Expiration;
+
+use crate::msg::PayoutMode;
+
+#[cw_serde]
+pub struct Config {
+    pub token_address: Addr,
+}
+
+#[cw_serde]
+pub struct Stage {
+    pub merkle_root: String,
+    pub total_amount: Uint128,
+    pub claimed_amount: Uint128,
+    pub expiration: Expiration,
+    pub payout: PayoutMode,
+    // set once the owner clawed back the remainder
+    pub closed: bool,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const LATEST_STAGE: Item<u64> = Item::new("latest_stage");
+pub const STAGES: Map<u64, Stage> = Map::new("stages");
+pub const CLAIMS: Map<(u64, &Addr), Uint128> = Map::new("claims");
\ No newline at end of file
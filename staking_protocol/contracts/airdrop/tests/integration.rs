@@ -0,0 +1,288 @@
+use cosmwasm_std::testing::MockApi;
+use cosmwasm_std::{Addr, Empty, Uint128};
+use cw_multi_test::{App, ContractWrapper, Executor};
+use cw_utils::Expiration;
+use sha2::{Digest, Sha256};
+
+use airdrop::ContractError;
+use airdrop::msg::{ExecuteMsg, InstantiateMsg, IsClaimedResponse, PayoutMode, QueryMsg, StageResponse};
+
+fn cw20_contract() -> Box<dyn cw_multi_test::Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw20_token::contract::execute,
+        cw20_token::contract::instantiate,
+        cw20_token::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn airdrop_contract() -> Box<dyn cw_multi_test::Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        airdrop::contract::execute,
+        airdrop::contract::instantiate,
+        airdrop::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn leaf(address: &str, amount: u128) -> [u8; 32] {
+    Sha256::digest(format!("{}{}", address, amount).as_bytes()).into()
+}
+
+fn hash_pair(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+    let (first, second) = if a <= b { (a, b) } else { (b, a) };
+    let mut hasher = Sha256::new();
+    hasher.update(first);
+    hasher.update(second);
+    hasher.finalize().into()
+}
+
+// (address, amount, proof)
+type Claim = (Addr, u128, Vec<String>);
+
+/// Tree over user1: 100, user2: 200, user3: 300, returns the root and
+/// each user's claim
+fn merkle_tree(api: &MockApi) -> (String, Vec<Claim>) {
+    let (user1, user2, user3) = (api.addr_make("user1"), api.addr_make("user2"), api.addr_make("user3"));
+    let l1 = leaf(user1.as_str(), 100);
+    let l2 = leaf(user2.as_str(), 200);
+    let l3 = leaf(user3.as_str(), 300);
+    let n12 = hash_pair(l1, l2);
+    let root = hash_pair(n12, l3);
+
+    let claims = vec![
+        (user1, 100, vec![hex::encode(l2), hex::encode(l3)]),
+        (user2, 200, vec![hex::encode(l1), hex::encode(l3)]),
+        (user3, 300, vec![hex::encode(n12)]),
+    ];
+    (hex::encode(root), claims)
+}
+
+struct TestSetup {
+    app: App,
+    token_addr: Addr,
+    airdrop_addr: Addr,
+    owner: Addr,
+}
+
+impl TestSetup {
+    fn new() -> Self {
+        let mut app = App::default();
+        let owner = app.api().addr_make("owner");
+        let cw20_code_id = app.store_code(cw20_contract());
+        let airdrop_code_id = app.store_code(airdrop_contract());
+
+        let token_addr = app
+            .instantiate_contract(
+                cw20_code_id,
+                owner.clone(),
+                &cw20_token::msg::InstantiateMsg {
+                    name: "Test Token".to_string(),
+                    symbol: "TST".to_string(),
+                    decimals: 18,
+                    initial_balances: vec![],
+                    owner: None,
+                    minter: Some(owner.to_string()),
+                    cap: None,
+                    marketing: None,
+                    compliance: false,
+                    transfer_fee_bps: None,
+                    fee_recipient: None,
+                },
+                &[],
+                "Test Token",
+                None,
+            )
+            .unwrap();
+
+        let airdrop_addr = app
+            .instantiate_contract(
+                airdrop_code_id,
+                owner.clone(),
+                &InstantiateMsg {
+                    owner: owner.to_string(),
+                    token_address: token_addr.to_string(),
+                },
+                &[],
+                "Airdrop",
+                None,
+            )
+            .unwrap();
+
+        TestSetup {
+            app,
+            token_addr,
+            airdrop_addr,
+            owner,
+        }
+    }
+
+    fn addr(&self, name: &str) -> Addr {
+        self.app.api().addr_make(name)
+    }
+
+    fn execute(&mut self, sender: &Addr, msg: &ExecuteMsg) -> Result<(), ContractError> {
+        self.app
+            .execute_contract(sender.clone(), self.airdrop_addr.clone(), msg, &[])
+            .map(|_| ())
+            .map_err(|err| err.downcast().unwrap())
+    }
+
+    fn execute_token(&mut self, sender: &Addr, msg: &cw20_token::msg::ExecuteMsg) {
+        self.app
+            .execute_contract(sender.clone(), self.token_addr.clone(), msg, &[])
+            .unwrap();
+    }
+
+    fn register(&mut self, root: &str, payout: PayoutMode, expiration: Option<Expiration>) {
+        let owner = self.owner.clone();
+        self.execute(&owner, &ExecuteMsg::RegisterMerkleRoot {
+            merkle_root: root.to_string(),
+            total_amount: Uint128::from(600u128),
+            expiration,
+            payout,
+        })
+        .unwrap();
+    }
+
+    fn balance(&self, address: &Addr) -> Uint128 {
+        let res: cw20_token::msg::BalanceResponse = self.app
+            .wrap()
+            .query_wasm_smart(&self.token_addr, &cw20_token::msg::QueryMsg::Balance {
+                address: address.to_string(),
+            })
+            .unwrap();
+        res.balance
+    }
+
+    fn is_claimed(&self, stage: u64, address: &Addr) -> bool {
+        let res: IsClaimedResponse = self.app
+            .wrap()
+            .query_wasm_smart(&self.airdrop_addr, &QueryMsg::IsClaimed {
+                stage,
+                address: address.to_string(),
+            })
+            .unwrap();
+        res.is_claimed
+    }
+}
+
+#[test]
+fn test_claim_from_balance_and_clawback() {
+    let mut setup = TestSetup::new();
+    let (root, claims) = merkle_tree(setup.app.api());
+    let owner = setup.owner.clone();
+
+    // funds the stage
+    let airdrop = setup.airdrop_addr.to_string();
+    setup.execute_token(&owner, &cw20_token::msg::ExecuteMsg::Mint {
+        recipient: airdrop,
+        amount: Uint128::from(600u128),
+    });
+
+    let err = setup.execute(&setup.addr("user1"), &ExecuteMsg::RegisterMerkleRoot {
+        merkle_root: root.clone(),
+        total_amount: Uint128::from(600u128),
+        expiration: None,
+        payout: PayoutMode::Transfer,
+    })
+    .unwrap_err();
+    assert_eq!(err.to_string(), ContractError::Ownership(cw_ownable::OwnershipError::NotOwner).to_string());
+
+    let err = setup.execute(&owner, &ExecuteMsg::RegisterMerkleRoot {
+        merkle_root: "not hex".to_string(),
+        total_amount: Uint128::from(600u128),
+        expiration: None,
+        payout: PayoutMode::Transfer,
+    })
+    .unwrap_err();
+    assert_eq!(err.to_string(), ContractError::InvalidMerkleRoot {}.to_string());
+
+    let expires_at = setup.app.block_info().height + 100;
+    setup.register(&root, PayoutMode::Transfer, Some(Expiration::AtHeight(expires_at)));
+
+    let (user1, amount1, proof1) = &claims[0];
+    let (user2, amount2, proof2) = &claims[1];
+
+    // a proof is bound to its own address and amount
+    let err = setup.execute(&setup.addr("user2"), &ExecuteMsg::Claim {
+        stage: 1,
+        amount: Uint128::from(*amount1),
+        proof: proof1.clone(),
+    })
+    .unwrap_err();
+    assert_eq!(err.to_string(), ContractError::VerificationFailed {}.to_string());
+
+    let claim1 = ExecuteMsg::Claim {
+        stage: 1,
+        amount: Uint128::from(*amount1),
+        proof: proof1.clone(),
+    };
+    setup.execute(user1, &claim1).unwrap();
+    assert_eq!(setup.balance(user1), Uint128::from(100u128));
+    assert!(setup.is_claimed(1, user1));
+    assert!(!setup.is_claimed(1, user2));
+
+    let err = setup.execute(user1, &claim1).unwrap_err();
+    assert_eq!(err.to_string(), ContractError::AlreadyClaimed {}.to_string());
+
+    let err = setup.execute(&owner, &ExecuteMsg::Clawback { stage: 1, recipient: None }).unwrap_err();
+    assert_eq!(err.to_string(), ContractError::StageNotExpired { stage: 1 }.to_string());
+
+    setup.app.update_block(|block| block.height = expires_at);
+    let treasury = setup.addr("treasury");
+
+    let err = setup.execute(user2, &ExecuteMsg::Claim {
+        stage: 1,
+        amount: Uint128::from(*amount2),
+        proof: proof2.clone(),
+    })
+    .unwrap_err();
+    assert_eq!(err.to_string(), ContractError::StageExpired { stage: 1 }.to_string());
+
+    setup.execute(&owner, &ExecuteMsg::Clawback {
+        stage: 1,
+        recipient: Some(treasury.to_string()),
+    })
+    .unwrap();
+    assert_eq!(setup.balance(&treasury), Uint128::from(500u128));
+    assert_eq!(setup.balance(&setup.airdrop_addr), Uint128::zero());
+
+    let stage: StageResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.airdrop_addr, &QueryMsg::Stage { stage: 1 })
+        .unwrap();
+    assert!(stage.closed);
+    assert_eq!(stage.claimed_amount, Uint128::from(100u128));
+}
+
+#[test]
+fn test_claim_by_minting() {
+    let mut setup = TestSetup::new();
+    let (root, claims) = merkle_tree(setup.app.api());
+    let owner = setup.owner.clone();
+
+    let airdrop = setup.airdrop_addr.to_string();
+    setup.execute_token(&owner, &cw20_token::msg::ExecuteMsg::SetMinter {
+        minter: airdrop,
+        allowance: Uint128::from(600u128),
+        period: 86_400,
+    });
+    setup.register(&root, PayoutMode::Mint, None);
+
+    for (user, amount, proof) in &claims {
+        setup.execute(user, &ExecuteMsg::Claim {
+            stage: 1,
+            amount: Uint128::from(*amount),
+            proof: proof.clone(),
+        })
+        .unwrap();
+        assert_eq!(setup.balance(user), Uint128::from(*amount));
+    }
+
+    let stage: StageResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.airdrop_addr, &QueryMsg::Stage { stage: 1 })
+        .unwrap();
+    assert_eq!(stage.claimed_amount, Uint128::from(600u128));
+}
\ No newline at end of file
@@ -1,7 +1,7 @@
 # Скрипт для оптимизации WASM файла, удаляющий bulk memory operations
 
-$WASM_INPUT = "target\wasm32-unknown-unknown\release\standart_cw20.wasm"
-$WASM_OUTPUT = "artifacts\standart_cw20.wasm"
+$WASM_INPUT = "..\..\target\wasm32-unknown-unknown\release\cw20_token.wasm"
+$WASM_OUTPUT = "artifacts\cw20_token.wasm"
 
 Write-Host "Оптимизация WASM файла..."
 
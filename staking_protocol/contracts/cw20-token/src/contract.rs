@@ -1,251 +1,1471 @@
-use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, entry_point, to_json_binary, Uint128, StdResult, StdError};
-use crate::error::ContractError;
-use crate::msg::{
-    AllowanceResponse, BalanceResponse, ExecuteMsg, InstantiateMsg, 
-    QueryMsg, TokenInfoResponse, MinterResponse,
-};
-use crate::state::{TokenInfo, TOKEN_INFO, BALANCES, ALLOWANCES};
-
-#[entry_point]
-pub fn instantiate (
-    deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
-    msg: InstantiateMsg,
-) -> Result<Response, ContractError> {
-    let minter = msg.minter
-        .map(|m| deps.api.addr_validate(&m))
-        .transpose()?;
-
-    let zero = Uint128::zero();
-
-    let token_info = TokenInfo{
-        name: msg.name,
-        symbol: msg.symbol,
-        decimals: msg.decimals,
-        total_supply: zero,
-        minter: minter,
-    };
-
-    TOKEN_INFO.save(deps.storage, &token_info)?;
-
-    Ok(Response::new()
-        .add_attribute("action", "instatiate"))
-}
-
-#[entry_point]
-pub fn execute (
-    deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
-    msg: ExecuteMsg,
-) -> Result<Response, ContractError> {
-    match msg {
-        ExecuteMsg::Transfer {recipient, amount} => {
-            execute_tranfer(deps, info, recipient, amount)
-        }
-        ExecuteMsg::Burn {amount} => {
-            execute_burn(deps, info, amount)
-        }
-        ExecuteMsg::Mint {recipient, amount} => {
-            execute_mint(deps, info, recipient, amount)
-        }
-        ExecuteMsg::Approve{spender, amount} => {
-            execute_approve(deps, info, spender, amount)
-        }
-        ExecuteMsg::TransferFrom{owner, recipient, amount} => {
-            execute_tranfer_from(deps, info, owner, recipient, amount)
-        }
-        ExecuteMsg::UpdateMinter {new_minter} => {
-            execute_update_minter(deps, info, new_minter)
-        }
-    }
-}
-
-#[entry_point]
-pub fn query (
-    deps: Deps,
-    _env: Env,
-    msg: QueryMsg,
-) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::Balance{address} => {
-            let addr = deps.api.addr_validate(&address)?;
-            let balance = BALANCES.may_load(deps.storage, &addr)?.unwrap_or_default();
-            to_json_binary(&BalanceResponse{balance})
-        }
-        QueryMsg::Allowance{owner, spender} => {
-            let owner = deps.api.addr_validate(&owner)?;
-            let spender = deps.api.addr_validate(&spender)?;
-            let allowance = ALLOWANCES.may_load(deps.storage, (&owner, &spender))?.unwrap_or_default();
-            to_json_binary(&AllowanceResponse{allowance})
-        }
-        QueryMsg::TokenInfo{} => {
-            let info = TOKEN_INFO.load(deps.storage)?;
-            to_json_binary(&TokenInfoResponse{
-                name: info.name,
-                symbol: info.symbol,
-                decimals: info.decimals,
-                total_supply: info.total_supply,
-            })
-        }
-        QueryMsg::Minter{} => {
-            let info = TOKEN_INFO.load(deps.storage)?;
-            to_json_binary(&MinterResponse{
-                minter: info.minter.map(|m| m.to_string()),
-            })
-        }
-    }
-}
-
-
-pub fn execute_tranfer(
-    deps: DepsMut,
-    info: MessageInfo,
-    recipient: String,
-    amount: Uint128,
-) -> Result<Response, ContractError> {
-    let rcpt = deps.api.addr_validate(&recipient)?;
-    
-    BALANCES.update(deps.storage, &info.sender, |bal| -> StdResult<_> {
-        let bal = bal.unwrap_or_default();
-        bal.checked_sub(amount)
-            .map_err(|_| StdError::generic_err("Insufficient balance"))
-    })?;
-
-    BALANCES.update(deps.storage, &rcpt, |bal| -> StdResult<_> {
-        Ok(bal.unwrap_or_default() + amount)
-    })?;
-
-    Ok(Response::new()
-        .add_attribute("action", "transfer")
-        .add_attribute("from", info.sender)
-        .add_attribute("to", rcpt)
-        .add_attribute("amount", amount))
-}
-
-pub fn execute_burn(
-    deps: DepsMut,
-    info: MessageInfo,
-    amount: Uint128,
-) ->Result<Response, ContractError> {
-    BALANCES.update(deps.storage, &info.sender, |bal| -> StdResult<_> {
-        let bal = bal.unwrap_or_default();
-        bal.checked_sub(amount)
-            .map_err(|_| StdError::generic_err("Insufficient balance"))
-    })?;
-
-    TOKEN_INFO.update(deps.storage, |mut info| -> StdResult<_> {
-        info.total_supply = info.total_supply.checked_sub(amount)?;
-        Ok(info)
-    })?;
-
-    Ok(Response::new()
-        .add_attribute("action", "burn")
-        .add_attribute("from", info.sender)
-        .add_attribute("amount", amount))
-}
-
-pub fn execute_mint(
-    deps: DepsMut,
-    info: MessageInfo,
-    recipient: String,
-    amount: Uint128,
-) -> Result<Response, ContractError> {
-    let token_info = TOKEN_INFO.load(deps.storage)?;
-
-    match token_info.minter {
-        Some(minter) if minter == info.sender => {},
-        _ => return Err(ContractError::Unauthorized {}),
-    }
-
-    let rcpt = deps.api.addr_validate(&recipient)?;
-
-    BALANCES.update(deps.storage, &rcpt, |bal| -> StdResult <_> {
-        Ok(bal.unwrap_or_default() + amount)
-    })?;
-
-    TOKEN_INFO.update(deps.storage, |mut info| -> StdResult<_> {
-        info.total_supply += amount;
-        Ok(info)
-    })?;
-
-    Ok(Response::new()
-        .add_attribute("action", "mint")
-        .add_attribute("to", recipient)
-        .add_attribute("amount", amount))
-}
-
-pub fn execute_approve(
-    deps: DepsMut,
-    info: MessageInfo,
-    spender: String,
-    amount: Uint128,
-) -> Result<Response, ContractError> {
-    let addr = deps.api.addr_validate(&spender)?;
-
-    ALLOWANCES.save(deps.storage, (&info.sender, &addr), &amount)?;
-
-    Ok(Response::new()
-        .add_attribute("action", "approve")
-        .add_attribute("owner", info.sender)
-        .add_attribute("spender", spender)
-        .add_attribute("amount", amount))
-}
-
-pub fn execute_tranfer_from(
-    deps: DepsMut,
-    info: MessageInfo,
-    owner: String,
-    recipient: String,
-    amount: Uint128,
-) -> Result<Response, ContractError> {
-    let owner_addr = deps.api.addr_validate(&owner)?;
-    let rcpt_addr = deps.api.addr_validate(&recipient)?;
-
-    let allowance = ALLOWANCES.may_load(deps.storage, (&owner_addr, &info.sender))?.unwrap_or_default();
-
-    if allowance < amount {
-        return Err(ContractError::InsufficientAllowance {});
-    }
-
-    ALLOWANCES.save(deps.storage, (&owner_addr, &info.sender), &(allowance-amount))?;
-
-    BALANCES.update(deps.storage, &owner_addr, |bal| -> StdResult<_> {
-        let bal = bal.unwrap_or_default();
-        bal.checked_sub(amount)
-            .map_err(|_|  StdError::generic_err("Insufficient balance"))
-    })?;
-
-    BALANCES.update(deps.storage, &rcpt_addr, |bal| -> StdResult<_>{
-        Ok(bal.unwrap_or_default() + amount)
-    })?;
-
-    Ok(Response::new()
-        .add_attribute("action", "transferFrom")
-        .add_attribute("owner", owner)
-        .add_attribute("recipient", recipient)
-        .add_attribute("amount", amount))
-}
-
-pub fn execute_update_minter(
-    deps: DepsMut,
-    info: MessageInfo,
-    new_minter: Option<String>,
-) -> Result<Response, ContractError> {
-    let mut token_info = TOKEN_INFO.load(deps.storage)?;
-
-    match token_info.minter {
-        Some(ref minter) if minter == &info.sender => {},
-        _ => return Err(ContractError::Unauthorized {}),
-    }
-
-    token_info.minter = new_minter
-        .map(|m| deps.api.addr_validate(&m))
-        .transpose()?;
-
-    TOKEN_INFO.save(deps.storage, &token_info)?;
-
-    Ok(Response::new()
-        .add_attribute("action", "execute_update_minter"))
+use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, entry_point, to_json_binary, Uint128, StdResult, StdError, Addr, Order, Storage, CanonicalAddr, Timestamp, to_json_vec, Empty, SubMsg};
+use cw20::{
+    Cw20ReceiveMsg, DownloadLogoResponse, EmbeddedLogo, Expiration, Logo, LogoInfo, MarketingInfoResponse,
+};
+use cw_storage_plus::Bound;
+use sha2::{Digest, Sha256};
+use shared::Cw20Coin;
+use crate::error::ContractError;
+use crate::msg::{
+    AllowanceResponse, BalanceResponse, ExecuteMsg, InstantiateMsg, InstantiateMode,
+    QueryMsg, TokenInfoResponse, MinterResponse, AllAllowancesResponse,
+    AllSpenderAllowancesResponse, AllAccountsResponse, SpenderAllowanceInfo,
+    AllowanceInfo as AllowanceInfoResponse, MinterQuotaResponse, MintersResponse,
+    TotalSupplyResponse, NonceResponse, PermitPayload, FrozenResponse, FrozenAccountsResponse,
+    PauseStatusResponse, QuoteTransferResponse, FeeExemptResponse, ComplianceStatusResponse,
+    HooksResponse, TransferHookMsg,
+};
+use crate::state::{
+    AllowanceInfo, TokenInfo, TOKEN_INFO, BALANCES, ALLOWANCES, ALLOWANCES_SPENDER,
+    MARKETING_INFO, LOGO, MinterQuota, MINTERS, TOTAL_SUPPLY, NONCES, FROZEN, PAUSED, FEE_EXEMPT,
+    TRANSFER_HOOKS,
+};
+
+const MIN_NAME_LENGTH: usize = 3;
+const MAX_NAME_LENGTH: usize = 50;
+const MIN_SYMBOL_LENGTH: usize = 3;
+const MAX_SYMBOL_LENGTH: usize = 12;
+const MAX_DECIMALS: u8 = 18;
+
+const MAX_TRANSFER_FEE_BPS: u16 = 1_000;
+const BPS_DENOMINATOR: u128 = 10_000;
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+const LOGO_SIZE_CAP: usize = 5 * 1024;
+
+const MAX_BATCH_SIZE: usize = 100;
+
+// every transfer messages each hook
+const MAX_TRANSFER_HOOKS: usize = 10;
+
+#[entry_point]
+pub fn instantiate (
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMode,
+) -> Result<Response, ContractError> {
+    let msg: InstantiateMsg = msg.into();
+    validate_token_info(&msg.name, &msg.symbol, msg.decimals)?;
+
+    let owner = msg.owner.as_deref().unwrap_or(info.sender.as_str());
+    cw_ownable::initialize_owner(deps.storage, deps.api, Some(owner))?;
+
+    let minter = msg.minter
+        .map(|m| deps.api.addr_validate(&m))
+        .transpose()?;
+
+    let total_supply = create_accounts(&mut deps, &env, &msg.initial_balances)?;
+    if msg.cap.is_some_and(|cap| total_supply > cap) {
+        return Err(ContractError::CannotExceedCap {});
+    }
+    TOTAL_SUPPLY.save(deps.storage, &total_supply, env.block.height)?;
+
+    let (transfer_fee_bps, fee_recipient) =
+        validate_transfer_fee(deps.as_ref(), msg.transfer_fee_bps, msg.fee_recipient)?;
+
+    let token_info = TokenInfo{
+        name: msg.name,
+        symbol: msg.symbol,
+        decimals: msg.decimals,
+        minter,
+        cap: msg.cap,
+        compliance: msg.compliance,
+        transfer_fee_bps,
+        fee_recipient,
+    };
+
+    TOKEN_INFO.save(deps.storage, &token_info)?;
+    PAUSED.save(deps.storage, &false)?;
+
+    if let Some(marketing) = msg.marketing {
+        let logo = match marketing.logo {
+            Some(logo) => {
+                verify_logo(&logo)?;
+                LOGO.save(deps.storage, &logo)?;
+                Some(logo_info(logo))
+            }
+            None => None,
+        };
+
+        MARKETING_INFO.save(deps.storage, &MarketingInfoResponse {
+            project: marketing.project,
+            description: marketing.description,
+            marketing: marketing.marketing
+                .map(|addr| deps.api.addr_validate(&addr))
+                .transpose()?,
+            logo,
+        })?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("owner", owner))
+}
+
+#[entry_point]
+pub fn execute (
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Transfer {recipient, amount} => {
+            execute_tranfer(deps, env, info, recipient, amount)
+        }
+        ExecuteMsg::Send {contract, amount, msg} => {
+            execute_send(deps, env, info, contract, amount, msg)
+        }
+        ExecuteMsg::Burn {amount} => {
+            execute_burn(deps, env, info, amount)
+        }
+        ExecuteMsg::Mint {recipient, amount} => {
+            execute_mint(deps, env, info, recipient, amount)
+        }
+        ExecuteMsg::BatchTransfer {transfers} => {
+            execute_batch_transfer(deps, env, info, transfers)
+        }
+        ExecuteMsg::BatchMint {mints} => {
+            execute_batch_mint(deps, env, info, mints)
+        }
+        ExecuteMsg::Approve{spender, amount} => {
+            execute_approve(deps, info, spender, amount)
+        }
+        ExecuteMsg::IncreaseAllowance{spender, amount, expires} => {
+            execute_increase_allowance(deps, env, info, spender, amount, expires)
+        }
+        ExecuteMsg::DecreaseAllowance{spender, amount, expires} => {
+            execute_decrease_allowance(deps, env, info, spender, amount, expires)
+        }
+        ExecuteMsg::TransferFrom{owner, recipient, amount} => {
+            execute_tranfer_from(deps, env, info, owner, recipient, amount)
+        }
+        ExecuteMsg::SendFrom{owner, contract, amount, msg} => {
+            execute_send_from(deps, env, info, owner, contract, amount, msg)
+        }
+        ExecuteMsg::BurnFrom{owner, amount} => {
+            execute_burn_from(deps, env, info, owner, amount)
+        }
+        ExecuteMsg::Permit{owner, spender, amount, expiration, nonce, signature, pubkey} => {
+            execute_permit(deps, env, owner, spender, amount, expiration, nonce, signature, pubkey)
+        }
+        ExecuteMsg::UpdateMinter {new_minter} => {
+            execute_update_minter(deps, info, new_minter)
+        }
+        ExecuteMsg::SetMinter {minter, allowance, period} => {
+            execute_set_minter(deps, env, info, minter, allowance, period)
+        }
+        ExecuteMsg::RemoveMinter {minter} => {
+            execute_remove_minter(deps, info, minter)
+        }
+        ExecuteMsg::UpdateOwnership(action) => {
+            cw_ownable::update_ownership(deps, &env.block, &info.sender, action)?;
+            Ok(Response::new().add_attribute("action", "update_ownership"))
+        }
+        ExecuteMsg::UpdateMarketing {project, description, marketing} => {
+            execute_update_marketing(deps, info, project, description, marketing)
+        }
+        ExecuteMsg::UploadLogo(logo) => {
+            execute_upload_logo(deps, info, logo)
+        }
+        ExecuteMsg::UpdateTransferFee{transfer_fee_bps, fee_recipient} => {
+            execute_update_transfer_fee(deps, info, transfer_fee_bps, fee_recipient)
+        }
+        ExecuteMsg::UpdateFeeExemptions{add, remove} => {
+            execute_update_fee_exemptions(deps, info, add, remove)
+        }
+        ExecuteMsg::Pause{} => {
+            execute_set_paused(deps, info, true)
+        }
+        ExecuteMsg::Unpause{} => {
+            execute_set_paused(deps, info, false)
+        }
+        ExecuteMsg::Freeze{address} => {
+            execute_set_frozen(deps, info, address, true)
+        }
+        ExecuteMsg::Unfreeze{address} => {
+            execute_set_frozen(deps, info, address, false)
+        }
+        ExecuteMsg::ForceBurn{address, amount} => {
+            execute_force_burn(deps, env, info, address, amount)
+        }
+        ExecuteMsg::AddHook{addr} => {
+            execute_add_hook(deps, info, addr)
+        }
+        ExecuteMsg::RemoveHook{addr} => {
+            execute_remove_hook(deps, info, addr)
+        }
+    }
+}
+
+#[entry_point]
+pub fn query (
+    deps: Deps,
+    env: Env,
+    msg: QueryMsg,
+) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Balance{address} => {
+            let addr = deps.api.addr_validate(&address)?;
+            let balance = BALANCES.may_load(deps.storage, &addr)?.unwrap_or_default();
+            to_json_binary(&BalanceResponse{balance})
+        }
+        QueryMsg::BalanceAtHeight{address, height} => {
+            let addr = deps.api.addr_validate(&address)?;
+            let balance = BALANCES.may_load_at_height(deps.storage, &addr, height)?.unwrap_or_default();
+            to_json_binary(&BalanceResponse{balance})
+        }
+        QueryMsg::TotalSupplyAtHeight{height} => {
+            let total_supply = TOTAL_SUPPLY.may_load_at_height(deps.storage, height)?.unwrap_or_default();
+            to_json_binary(&TotalSupplyResponse{total_supply})
+        }
+        QueryMsg::Allowance{owner, spender} => {
+            let owner = deps.api.addr_validate(&owner)?;
+            let spender = deps.api.addr_validate(&spender)?;
+            let info = ALLOWANCES.may_load(deps.storage, (&owner, &spender))?
+                .filter(|a| !a.expires.is_expired(&env.block))
+                .unwrap_or_default();
+            to_json_binary(&AllowanceResponse{
+                allowance: info.allowance,
+                expires: info.expires,
+            })
+        }
+        QueryMsg::QuoteTransfer{sender, recipient, amount} => {
+            to_json_binary(&query_quote_transfer(deps, sender, recipient, amount)?)
+        }
+        QueryMsg::FeeExempt{address} => {
+            let addr = deps.api.addr_validate(&address)?;
+            to_json_binary(&FeeExemptResponse{exempt: FEE_EXEMPT.has(deps.storage, &addr)})
+        }
+        QueryMsg::PauseStatus{} => {
+            let paused = PAUSED.may_load(deps.storage)?.unwrap_or_default();
+            to_json_binary(&PauseStatusResponse{paused})
+        }
+        QueryMsg::ComplianceStatus{} => {
+            let compliance = TOKEN_INFO.load(deps.storage)?.compliance;
+            to_json_binary(&ComplianceStatusResponse{compliance})
+        }
+        QueryMsg::Frozen{address} => {
+            let addr = deps.api.addr_validate(&address)?;
+            to_json_binary(&FrozenResponse{frozen: FROZEN.has(deps.storage, &addr)})
+        }
+        QueryMsg::FrozenAccounts{start_after, limit} => {
+            to_json_binary(&query_frozen_accounts(deps, start_after, limit)?)
+        }
+        QueryMsg::Nonce{owner} => {
+            let owner = deps.api.addr_validate(&owner)?;
+            let nonce = NONCES.may_load(deps.storage, &owner)?.unwrap_or_default();
+            to_json_binary(&NonceResponse{nonce})
+        }
+        QueryMsg::TokenInfo{} => {
+            let info = TOKEN_INFO.load(deps.storage)?;
+            to_json_binary(&TokenInfoResponse{
+                name: info.name,
+                symbol: info.symbol,
+                decimals: info.decimals,
+                total_supply: TOTAL_SUPPLY.load(deps.storage)?,
+            })
+        }
+        QueryMsg::Minter{} => {
+            let info = TOKEN_INFO.load(deps.storage)?;
+            to_json_binary(&MinterResponse{
+                minter: info.minter.map(|m| m.to_string()),
+                cap: info.cap,
+            })
+        }
+        QueryMsg::Minters{start_after, limit} => {
+            to_json_binary(&query_minters(deps, env, start_after, limit)?)
+        }
+        QueryMsg::Hooks{} => {
+            let hooks = TRANSFER_HOOKS
+                .keys(deps.storage, None, None, Order::Ascending)
+                .map(|hook| hook.map(String::from))
+                .collect::<StdResult<_>>()?;
+            to_json_binary(&HooksResponse{hooks})
+        }
+        QueryMsg::Ownership {} => {
+            to_json_binary(&cw_ownable::get_ownership(deps.storage)?)
+        }
+        QueryMsg::MarketingInfo{} => {
+            let info = MARKETING_INFO.may_load(deps.storage)?.unwrap_or_default();
+            to_json_binary(&info)
+        }
+        QueryMsg::DownloadLogo{} => {
+            to_json_binary(&query_download_logo(deps)?)
+        }
+        QueryMsg::AllAllowances{owner, start_after, limit} => {
+            to_json_binary(&query_all_allowances(deps, env, owner, start_after, limit)?)
+        }
+        QueryMsg::AllSpenderAllowances{spender, start_after, limit} => {
+            to_json_binary(&query_all_spender_allowances(deps, env, spender, start_after, limit)?)
+        }
+        QueryMsg::AllAccounts{start_after, limit} => {
+            to_json_binary(&query_all_accounts(deps, start_after, limit)?)
+        }
+    }
+}
+
+
+pub fn execute_tranfer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    assert_not_paused(deps.storage)?;
+    let rcpt = deps.api.addr_validate(&recipient)?;
+    assert_not_frozen(deps.storage, &[&info.sender, &rcpt])?;
+
+    BALANCES.update(deps.storage, &info.sender, env.block.height, |bal| {
+        bal.unwrap_or_default()
+            .checked_sub(amount)
+            .map_err(|_| ContractError::InsufficientBalance {})
+    })?;
+
+    let fee = credit_transfer(deps.storage, &env, &info.sender, &rcpt, amount)?;
+    let hooks = transfer_hooks(deps.storage, &info.sender, &rcpt, amount - fee)?;
+
+    Ok(Response::new()
+        .add_submessages(hooks)
+        .add_attribute("action", "transfer")
+        .add_attribute("from", info.sender)
+        .add_attribute("to", rcpt)
+        .add_attribute("amount", amount)
+        .add_attribute("fee", fee))
+}
+
+pub fn execute_send(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    contract: String,
+    amount: Uint128,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    assert_not_paused(deps.storage)?;
+    let rcpt = deps.api.addr_validate(&contract)?;
+    assert_not_frozen(deps.storage, &[&info.sender, &rcpt])?;
+
+    BALANCES.update(deps.storage, &info.sender, env.block.height, |bal| {
+        bal.unwrap_or_default()
+            .checked_sub(amount)
+            .map_err(|_| ContractError::InsufficientBalance {})
+    })?;
+
+    let fee = credit_transfer(deps.storage, &env, &info.sender, &rcpt, amount)?;
+    let hooks = transfer_hooks(deps.storage, &info.sender, &rcpt, amount - fee)?;
+    let receive = Cw20ReceiveMsg {
+        sender: info.sender.to_string(),
+        amount: amount - fee,
+        msg,
+    }
+    .into_cosmos_msg(&rcpt)?;
+
+    Ok(Response::new()
+        .add_message(receive)
+        .add_submessages(hooks)
+        .add_attribute("action", "send")
+        .add_attribute("from", info.sender)
+        .add_attribute("to", rcpt)
+        .add_attribute("amount", amount)
+        .add_attribute("fee", fee))
+}
+
+pub fn execute_burn(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+) ->Result<Response, ContractError> {
+    assert_not_paused(deps.storage)?;
+    assert_not_frozen(deps.storage, &[&info.sender])?;
+
+    BALANCES.update(deps.storage, &info.sender, env.block.height, |bal| {
+        bal.unwrap_or_default()
+            .checked_sub(amount)
+            .map_err(|_| ContractError::InsufficientBalance {})
+    })?;
+
+    TOTAL_SUPPLY.update(deps.storage, env.block.height, |supply| -> StdResult<_> {
+        Ok(supply.unwrap_or_default().checked_sub(amount)?)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "burn")
+        .add_attribute("from", info.sender)
+        .add_attribute("amount", amount))
+}
+
+pub fn execute_mint(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    assert_not_paused(deps.storage)?;
+    let token_info = TOKEN_INFO.load(deps.storage)?;
+    assert_mintable(deps.storage, &token_info)?;
+
+    if token_info.minter.as_ref() != Some(&info.sender) {
+        let mut quota = MINTERS
+            .may_load(deps.storage, &info.sender)?
+            .ok_or(ContractError::Unauthorized {})?;
+
+        quota.roll(env.block.time);
+        let remaining = quota.remaining();
+        if amount > remaining {
+            return Err(ContractError::MintQuotaExceeded { remaining });
+        }
+        quota.minted += amount;
+        MINTERS.save(deps.storage, &info.sender, &quota)?;
+    }
+
+    let total_supply = TOTAL_SUPPLY.load(deps.storage)? + amount;
+    if token_info.cap.is_some_and(|cap| total_supply > cap) {
+        return Err(ContractError::CannotExceedCap {});
+    }
+
+    let rcpt = deps.api.addr_validate(&recipient)?;
+
+    BALANCES.update(deps.storage, &rcpt, env.block.height, |bal| -> StdResult <_> {
+        Ok(bal.unwrap_or_default() + amount)
+    })?;
+
+    TOTAL_SUPPLY.save(deps.storage, &total_supply, env.block.height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "mint")
+        .add_attribute("to", rcpt)
+        .add_attribute("amount", amount))
+}
+
+pub fn execute_batch_transfer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    transfers: Vec<Cw20Coin>,
+) -> Result<Response, ContractError> {
+    assert_not_paused(deps.storage)?;
+    check_batch_size(&transfers)?;
+    assert_not_frozen(deps.storage, &[&info.sender])?;
+
+    let mut total = Uint128::zero();
+    let mut fees = Uint128::zero();
+    let mut hooks = vec![];
+    for transfer in &transfers {
+        let rcpt = deps.api.addr_validate(&transfer.address)?;
+        assert_not_frozen(deps.storage, &[&rcpt])?;
+        let fee = credit_transfer(deps.storage, &env, &info.sender, &rcpt, transfer.amount)?;
+        hooks.extend(transfer_hooks(deps.storage, &info.sender, &rcpt, transfer.amount - fee)?);
+        fees += fee;
+        total += transfer.amount;
+    }
+
+    // debited once for the whole batch, a self transfer nets out
+    BALANCES.update(deps.storage, &info.sender, env.block.height, |bal| {
+        bal.unwrap_or_default()
+            .checked_sub(total)
+            .map_err(|_| ContractError::InsufficientBalance {})
+    })?;
+
+    Ok(Response::new()
+        .add_submessages(hooks)
+        .add_attribute("action", "batch_transfer")
+        .add_attribute("from", info.sender)
+        .add_attribute("count", transfers.len().to_string())
+        .add_attribute("amount", total)
+        .add_attribute("fee", fees))
+}
+
+pub fn execute_batch_mint(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    mints: Vec<Cw20Coin>,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    assert_not_paused(deps.storage)?;
+    check_batch_size(&mints)?;
+
+    let token_info = TOKEN_INFO.load(deps.storage)?;
+    if token_info.minter.is_none() {
+        return Err(ContractError::MintingDisabled {});
+    }
+    let mut total_supply = TOTAL_SUPPLY.load(deps.storage)?;
+    let mut total = Uint128::zero();
+    for mint in &mints {
+        let rcpt = deps.api.addr_validate(&mint.address)?;
+        BALANCES.update(deps.storage, &rcpt, env.block.height, |bal| -> StdResult<_> {
+            Ok(bal.unwrap_or_default() + mint.amount)
+        })?;
+        total += mint.amount;
+    }
+
+    total_supply += total;
+    if token_info.cap.is_some_and(|cap| total_supply > cap) {
+        return Err(ContractError::CannotExceedCap {});
+    }
+    TOTAL_SUPPLY.save(deps.storage, &total_supply, env.block.height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "batch_mint")
+        .add_attribute("count", mints.len().to_string())
+        .add_attribute("amount", total))
+}
+
+pub fn execute_approve(
+    deps: DepsMut,
+    info: MessageInfo,
+    spender: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let addr = deps.api.addr_validate(&spender)?;
+    if addr == info.sender {
+        return Err(ContractError::CannotSetOwnAccount {});
+    }
+
+    save_allowance(deps.storage, &info.sender, &addr, &AllowanceInfo {
+        allowance: amount,
+        expires: Expiration::Never {},
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "approve")
+        .add_attribute("owner", info.sender)
+        .add_attribute("spender", spender)
+        .add_attribute("amount", amount))
+}
+
+pub fn execute_increase_allowance(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    spender: String,
+    amount: Uint128,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let spender_addr = deps.api.addr_validate(&spender)?;
+    if spender_addr == info.sender {
+        return Err(ContractError::CannotSetOwnAccount {});
+    }
+
+    // an expired allowance starts over from zero
+    let mut allowance = ALLOWANCES.may_load(deps.storage, (&info.sender, &spender_addr))?
+        .filter(|a| !a.expires.is_expired(&env.block))
+        .unwrap_or_default();
+    if let Some(exp) = expires {
+        if exp.is_expired(&env.block) {
+            return Err(ContractError::InvalidExpiration {});
+        }
+        allowance.expires = exp;
+    }
+    allowance.allowance += amount;
+    save_allowance(deps.storage, &info.sender, &spender_addr, &allowance)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "increase_allowance")
+        .add_attribute("owner", info.sender)
+        .add_attribute("spender", spender)
+        .add_attribute("amount", amount))
+}
+
+pub fn execute_decrease_allowance(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    spender: String,
+    amount: Uint128,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let spender_addr = deps.api.addr_validate(&spender)?;
+    if spender_addr == info.sender {
+        return Err(ContractError::CannotSetOwnAccount {});
+    }
+
+    let mut allowance = ALLOWANCES.may_load(deps.storage, (&info.sender, &spender_addr))?
+        .filter(|a| !a.expires.is_expired(&env.block))
+        .unwrap_or_default();
+
+    allowance.allowance = allowance.allowance.saturating_sub(amount);
+    if allowance.allowance.is_zero() {
+        remove_allowance(deps.storage, &info.sender, &spender_addr);
+    } else {
+        if let Some(exp) = expires {
+            if exp.is_expired(&env.block) {
+                return Err(ContractError::InvalidExpiration {});
+            }
+            allowance.expires = exp;
+        }
+        save_allowance(deps.storage, &info.sender, &spender_addr, &allowance)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "decrease_allowance")
+        .add_attribute("owner", info.sender)
+        .add_attribute("spender", spender)
+        .add_attribute("amount", amount))
+}
+
+pub fn execute_tranfer_from(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner: String,
+    recipient: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    assert_not_paused(deps.storage)?;
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let rcpt_addr = deps.api.addr_validate(&recipient)?;
+    assert_not_frozen(deps.storage, &[&owner_addr, &info.sender, &rcpt_addr])?;
+
+    let mut allowance = ALLOWANCES.may_load(deps.storage, (&owner_addr, &info.sender))?.unwrap_or_default();
+
+    if allowance.expires.is_expired(&env.block) {
+        return Err(ContractError::Expired {});
+    }
+    if allowance.allowance < amount {
+        return Err(ContractError::InsufficientAllowance {});
+    }
+
+    allowance.allowance -= amount;
+    save_allowance(deps.storage, &owner_addr, &info.sender, &allowance)?;
+
+    BALANCES.update(deps.storage, &owner_addr, env.block.height, |bal| {
+        bal.unwrap_or_default()
+            .checked_sub(amount)
+            .map_err(|_| ContractError::InsufficientBalance {})
+    })?;
+
+    let fee = credit_transfer(deps.storage, &env, &owner_addr, &rcpt_addr, amount)?;
+    let hooks = transfer_hooks(deps.storage, &owner_addr, &rcpt_addr, amount - fee)?;
+
+    Ok(Response::new()
+        .add_submessages(hooks)
+        .add_attribute("action", "transfer_from")
+        .add_attribute("from", owner_addr)
+        .add_attribute("to", rcpt_addr)
+        .add_attribute("by", info.sender)
+        .add_attribute("amount", amount)
+        .add_attribute("fee", fee))
+}
+
+pub fn execute_send_from(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner: String,
+    contract: String,
+    amount: Uint128,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    assert_not_paused(deps.storage)?;
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let rcpt_addr = deps.api.addr_validate(&contract)?;
+    assert_not_frozen(deps.storage, &[&owner_addr, &info.sender, &rcpt_addr])?;
+
+    let mut allowance = ALLOWANCES.may_load(deps.storage, (&owner_addr, &info.sender))?.unwrap_or_default();
+    if allowance.expires.is_expired(&env.block) {
+        return Err(ContractError::Expired {});
+    }
+    if allowance.allowance < amount {
+        return Err(ContractError::InsufficientAllowance {});
+    }
+    allowance.allowance -= amount;
+    save_allowance(deps.storage, &owner_addr, &info.sender, &allowance)?;
+
+    BALANCES.update(deps.storage, &owner_addr, env.block.height, |bal| {
+        bal.unwrap_or_default()
+            .checked_sub(amount)
+            .map_err(|_| ContractError::InsufficientBalance {})
+    })?;
+
+    let fee = credit_transfer(deps.storage, &env, &owner_addr, &rcpt_addr, amount)?;
+    let hooks = transfer_hooks(deps.storage, &owner_addr, &rcpt_addr, amount - fee)?;
+    // the hook sees the spender as the sender, like cw20-base
+    let receive = Cw20ReceiveMsg {
+        sender: info.sender.to_string(),
+        amount: amount - fee,
+        msg,
+    }
+    .into_cosmos_msg(&rcpt_addr)?;
+
+    Ok(Response::new()
+        .add_message(receive)
+        .add_submessages(hooks)
+        .add_attribute("action", "send_from")
+        .add_attribute("from", owner_addr)
+        .add_attribute("to", rcpt_addr)
+        .add_attribute("by", info.sender)
+        .add_attribute("amount", amount)
+        .add_attribute("fee", fee))
+}
+
+pub fn execute_burn_from(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    assert_not_paused(deps.storage)?;
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    assert_not_frozen(deps.storage, &[&owner_addr, &info.sender])?;
+
+    let mut allowance = ALLOWANCES.may_load(deps.storage, (&owner_addr, &info.sender))?.unwrap_or_default();
+    if allowance.expires.is_expired(&env.block) {
+        return Err(ContractError::Expired {});
+    }
+    if allowance.allowance < amount {
+        return Err(ContractError::InsufficientAllowance {});
+    }
+    allowance.allowance -= amount;
+    save_allowance(deps.storage, &owner_addr, &info.sender, &allowance)?;
+
+    BALANCES.update(deps.storage, &owner_addr, env.block.height, |bal| {
+        bal.unwrap_or_default()
+            .checked_sub(amount)
+            .map_err(|_| ContractError::InsufficientBalance {})
+    })?;
+
+    TOTAL_SUPPLY.update(deps.storage, env.block.height, |supply| -> StdResult<_> {
+        Ok(supply.unwrap_or_default().checked_sub(amount)?)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "burn_from")
+        .add_attribute("from", owner_addr)
+        .add_attribute("by", info.sender)
+        .add_attribute("amount", amount))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_permit(
+    deps: DepsMut,
+    env: Env,
+    owner: String,
+    spender: String,
+    amount: Uint128,
+    expiration: Timestamp,
+    nonce: u64,
+    signature: Binary,
+    pubkey: Binary,
+) -> Result<Response, ContractError> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let spender_addr = deps.api.addr_validate(&spender)?;
+    if owner_addr == spender_addr {
+        return Err(ContractError::CannotSetOwnAccount {});
+    }
+
+    if env.block.time >= expiration {
+        return Err(ContractError::PermitExpired {});
+    }
+
+    let expected = NONCES.may_load(deps.storage, &owner_addr)?.unwrap_or_default();
+    if nonce != expected {
+        return Err(ContractError::InvalidNonce { expected });
+    }
+
+    // ed25519 account addresses are the first 20 bytes of sha256(pubkey)
+    let hash = Sha256::digest(pubkey.as_slice());
+    let signer = deps.api.addr_humanize(&CanonicalAddr::from(&hash[..20]))?;
+    if signer != owner_addr {
+        return Err(ContractError::PubkeyMismatch {});
+    }
+
+    let payload = to_json_vec(&PermitPayload {
+        chain_id: env.block.chain_id,
+        contract: env.contract.address.to_string(),
+        owner,
+        spender,
+        amount,
+        expiration,
+        nonce,
+    })?;
+    let valid = deps.api
+        .ed25519_verify(&payload, &signature, &pubkey)
+        .map_err(|_| ContractError::InvalidSignature {})?;
+    if !valid {
+        return Err(ContractError::InvalidSignature {});
+    }
+
+    NONCES.save(deps.storage, &owner_addr, &(nonce + 1))?;
+    save_allowance(deps.storage, &owner_addr, &spender_addr, &AllowanceInfo {
+        allowance: amount,
+        expires: Expiration::Never {},
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "permit")
+        .add_attribute("owner", owner_addr)
+        .add_attribute("spender", spender_addr)
+        .add_attribute("amount", amount)
+        .add_attribute("nonce", nonce.to_string()))
+}
+
+pub fn execute_update_minter(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_minter: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut token_info = TOKEN_INFO.load(deps.storage)?;
+
+    match token_info.minter {
+        Some(ref minter) if *minter == info.sender => {},
+        _ => return Err(ContractError::Unauthorized {}),
+    }
+
+    token_info.minter = new_minter
+        .map(|m| deps.api.addr_validate(&m))
+        .transpose()?;
+
+    TOKEN_INFO.save(deps.storage, &token_info)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "execute_update_minter"))
+}
+
+pub fn execute_set_minter(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    minter: String,
+    allowance: Uint128,
+    period: u64,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    assert_mintable(deps.storage, &TOKEN_INFO.load(deps.storage)?)?;
+    if period == 0 {
+        return Err(ContractError::InvalidMintPeriod {});
+    }
+
+    let minter = deps.api.addr_validate(&minter)?;
+    // a quota update starts a fresh period
+    MINTERS.save(deps.storage, &minter, &MinterQuota {
+        allowance,
+        period,
+        minted: Uint128::zero(),
+        period_start: env.block.time,
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_minter")
+        .add_attribute("minter", minter)
+        .add_attribute("allowance", allowance)
+        .add_attribute("period", period.to_string()))
+}
+
+pub fn execute_remove_minter(
+    deps: DepsMut,
+    info: MessageInfo,
+    minter: String,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let minter = deps.api.addr_validate(&minter)?;
+    MINTERS.remove(deps.storage, &minter);
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_minter")
+        .add_attribute("minter", minter))
+}
+
+pub fn execute_update_marketing(
+    deps: DepsMut,
+    info: MessageInfo,
+    project: Option<String>,
+    description: Option<String>,
+    marketing: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut marketing_info = MARKETING_INFO
+        .may_load(deps.storage)?
+        .ok_or(ContractError::Unauthorized {})?;
+
+    if marketing_info.marketing.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // an empty string clears the field, None leaves it untouched
+    if let Some(project) = project {
+        marketing_info.project = Some(project).filter(|p| !p.trim().is_empty());
+    }
+    if let Some(description) = description {
+        marketing_info.description = Some(description).filter(|d| !d.trim().is_empty());
+    }
+    if let Some(marketing) = marketing {
+        marketing_info.marketing = match marketing.trim() {
+            "" => None,
+            addr => Some(deps.api.addr_validate(addr)?),
+        };
+    }
+
+    if marketing_info.project.is_none()
+        && marketing_info.description.is_none()
+        && marketing_info.marketing.is_none()
+        && marketing_info.logo.is_none()
+    {
+        MARKETING_INFO.remove(deps.storage);
+    } else {
+        MARKETING_INFO.save(deps.storage, &marketing_info)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "update_marketing"))
+}
+
+pub fn execute_upload_logo(
+    deps: DepsMut,
+    info: MessageInfo,
+    logo: Logo,
+) -> Result<Response, ContractError> {
+    let mut marketing_info = MARKETING_INFO
+        .may_load(deps.storage)?
+        .ok_or(ContractError::Unauthorized {})?;
+
+    verify_logo(&logo)?;
+
+    if marketing_info.marketing.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    LOGO.save(deps.storage, &logo)?;
+    marketing_info.logo = Some(logo_info(logo));
+    MARKETING_INFO.save(deps.storage, &marketing_info)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "upload_logo"))
+}
+
+pub fn execute_update_transfer_fee(
+    deps: DepsMut,
+    info: MessageInfo,
+    transfer_fee_bps: Option<u16>,
+    fee_recipient: Option<String>,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let (transfer_fee_bps, fee_recipient) =
+        validate_transfer_fee(deps.as_ref(), transfer_fee_bps, fee_recipient)?;
+
+    TOKEN_INFO.update(deps.storage, |mut token_info| -> StdResult<_> {
+        token_info.transfer_fee_bps = transfer_fee_bps;
+        token_info.fee_recipient = fee_recipient.clone();
+        Ok(token_info)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_transfer_fee")
+        .add_attribute("transfer_fee_bps", transfer_fee_bps.unwrap_or_default().to_string())
+        .add_attribute("fee_recipient", fee_recipient.map(String::from).unwrap_or_default()))
+}
+
+pub fn execute_update_fee_exemptions(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    for address in &add {
+        let addr = deps.api.addr_validate(address)?;
+        FEE_EXEMPT.save(deps.storage, &addr, &Empty {})?;
+    }
+    for address in &remove {
+        let addr = deps.api.addr_validate(address)?;
+        FEE_EXEMPT.remove(deps.storage, &addr);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "update_fee_exemptions")
+        .add_attribute("added", add.len().to_string())
+        .add_attribute("removed", remove.len().to_string()))
+}
+
+pub fn execute_set_paused(
+    deps: DepsMut,
+    info: MessageInfo,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    PAUSED.save(deps.storage, &paused)?;
+
+    Ok(Response::new()
+        .add_attribute("action", if paused { "pause" } else { "unpause" })
+        .add_attribute("owner", info.sender))
+}
+
+pub fn execute_set_frozen(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+    frozen: bool,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    if !TOKEN_INFO.load(deps.storage)?.compliance {
+        return Err(ContractError::ComplianceDisabled {});
+    }
+
+    let addr = deps.api.addr_validate(&address)?;
+    if frozen {
+        FROZEN.save(deps.storage, &addr, &Empty {})?;
+    } else {
+        FROZEN.remove(deps.storage, &addr);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", if frozen { "freeze" } else { "unfreeze" })
+        .add_attribute("address", addr))
+}
+
+pub fn execute_add_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    addr: String,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let hook = deps.api.addr_validate(&addr)?;
+    if TRANSFER_HOOKS.has(deps.storage, &hook) {
+        return Err(ContractError::HookAlreadyRegistered {});
+    }
+    let count = TRANSFER_HOOKS.keys_raw(deps.storage, None, None, Order::Ascending).count();
+    if count >= MAX_TRANSFER_HOOKS {
+        return Err(ContractError::TooManyHooks { max: MAX_TRANSFER_HOOKS });
+    }
+    TRANSFER_HOOKS.save(deps.storage, &hook, &Empty {})?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_hook")
+        .add_attribute("hook", hook))
+}
+
+pub fn execute_remove_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    addr: String,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let hook = deps.api.addr_validate(&addr)?;
+    if !TRANSFER_HOOKS.has(deps.storage, &hook) {
+        return Err(ContractError::HookNotRegistered {});
+    }
+    TRANSFER_HOOKS.remove(deps.storage, &hook);
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_hook")
+        .add_attribute("hook", hook))
+}
+
+pub fn execute_force_burn(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    if !TOKEN_INFO.load(deps.storage)?.compliance {
+        return Err(ContractError::ComplianceDisabled {});
+    }
+    assert_not_paused(deps.storage)?;
+
+    let addr = deps.api.addr_validate(&address)?;
+    BALANCES.update(deps.storage, &addr, env.block.height, |bal| {
+        bal.unwrap_or_default()
+            .checked_sub(amount)
+            .map_err(|_| ContractError::InsufficientBalance {})
+    })?;
+
+    TOTAL_SUPPLY.update(deps.storage, env.block.height, |supply| -> StdResult<_> {
+        Ok(supply.unwrap_or_default().checked_sub(amount)?)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "force_burn")
+        .add_attribute("from", addr)
+        .add_attribute("by", info.sender)
+        .add_attribute("amount", amount))
+}
+
+fn verify_logo(logo: &Logo) -> Result<(), ContractError> {
+    match logo {
+        Logo::Url(_) => Ok(()),
+        Logo::Embedded(EmbeddedLogo::Svg(svg)) => {
+            if svg.len() > LOGO_SIZE_CAP {
+                return Err(ContractError::LogoTooBig {});
+            }
+            let preamble = svg.iter()
+                .skip_while(|b| b.is_ascii_whitespace())
+                .take(5)
+                .cloned()
+                .collect::<Vec<u8>>();
+            if preamble != b"<?xml" && preamble != b"<svg " {
+                return Err(ContractError::InvalidXmlPreamble {});
+            }
+            Ok(())
+        }
+        Logo::Embedded(EmbeddedLogo::Png(png)) => {
+            const PNG_HEADER: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+            if png.len() > LOGO_SIZE_CAP {
+                return Err(ContractError::LogoTooBig {});
+            }
+            if !png.starts_with(&PNG_HEADER) {
+                return Err(ContractError::InvalidPngHeader {});
+            }
+            Ok(())
+        }
+    }
+}
+
+fn logo_info(logo: Logo) -> LogoInfo {
+    match logo {
+        Logo::Url(url) => LogoInfo::Url(url),
+        Logo::Embedded(_) => LogoInfo::Embedded,
+    }
+}
+
+fn validate_transfer_fee(
+    deps: Deps,
+    transfer_fee_bps: Option<u16>,
+    fee_recipient: Option<String>,
+) -> Result<(Option<u16>, Option<Addr>), ContractError> {
+    match (transfer_fee_bps, fee_recipient) {
+        (None, None) => Ok((None, None)),
+        (Some(bps), Some(recipient)) if bps <= MAX_TRANSFER_FEE_BPS => {
+            Ok((Some(bps), Some(deps.api.addr_validate(&recipient)?)))
+        }
+        _ => Err(ContractError::InvalidTransferFee { max: MAX_TRANSFER_FEE_BPS }),
+    }
+}
+
+/// Fee owed on a transfer, nothing when either side is exempt or is the
+/// fee recipient itself
+fn transfer_fee(
+    storage: &dyn Storage,
+    token_info: &TokenInfo,
+    from: &Addr,
+    to: &Addr,
+    amount: Uint128,
+) -> StdResult<Uint128> {
+    let (Some(bps), Some(fee_recipient)) = (token_info.transfer_fee_bps, &token_info.fee_recipient) else {
+        return Ok(Uint128::zero());
+    };
+    if from == fee_recipient
+        || to == fee_recipient
+        || FEE_EXEMPT.has(storage, from)
+        || FEE_EXEMPT.has(storage, to)
+    {
+        return Ok(Uint128::zero());
+    }
+    Ok(amount.multiply_ratio(bps, BPS_DENOMINATOR))
+}
+
+/// Credits the recipient with the amount net of fees, the fee goes to
+/// the fee recipient. The sender has to be debited by the caller.
+fn credit_transfer(
+    storage: &mut dyn Storage,
+    env: &Env,
+    from: &Addr,
+    to: &Addr,
+    amount: Uint128,
+) -> Result<Uint128, ContractError> {
+    // nothing could move tokens back out of the token contract
+    if *to == env.contract.address {
+        return Err(ContractError::TransferToSelf {});
+    }
+    let height = env.block.height;
+    let token_info = TOKEN_INFO.load(storage)?;
+    let fee = transfer_fee(storage, &token_info, from, to, amount)?;
+
+    BALANCES.update(storage, to, height, |bal| -> StdResult<_> {
+        Ok(bal.unwrap_or_default() + amount - fee)
+    })?;
+    if let Some(fee_recipient) = token_info.fee_recipient.filter(|_| !fee.is_zero()) {
+        BALANCES.update(storage, &fee_recipient, height, |bal| -> StdResult<_> {
+            Ok(bal.unwrap_or_default() + fee)
+        })?;
+    }
+
+    Ok(fee)
+}
+
+/// Notifies every registered hook of a transfer that credited `to` with `amount`
+fn transfer_hooks(storage: &dyn Storage, from: &Addr, to: &Addr, amount: Uint128) -> StdResult<Vec<SubMsg>> {
+    TRANSFER_HOOKS
+        .keys(storage, None, None, Order::Ascending)
+        .map(|hook| {
+            TransferHookMsg {
+                from: from.to_string(),
+                to: to.to_string(),
+                amount,
+            }
+            .into_cosmos_msg(hook?)
+            .map(SubMsg::new)
+        })
+        .collect()
+}
+
+fn assert_not_paused(storage: &dyn Storage) -> Result<(), ContractError> {
+    if PAUSED.may_load(storage)?.unwrap_or_default() {
+        return Err(ContractError::Paused {});
+    }
+    Ok(())
+}
+
+/// Without an unrestricted minter and without registry minters the supply
+/// is fixed, as in cw20-base, and stays that way
+fn assert_mintable(storage: &dyn Storage, token_info: &TokenInfo) -> Result<(), ContractError> {
+    if token_info.minter.is_none() && MINTERS.is_empty(storage) {
+        return Err(ContractError::MintingDisabled {});
+    }
+    Ok(())
+}
+
+fn assert_not_frozen(storage: &dyn Storage, accounts: &[&Addr]) -> Result<(), ContractError> {
+    match accounts.iter().find(|addr| FROZEN.has(storage, addr)) {
+        Some(addr) => Err(ContractError::AccountFrozen { address: addr.to_string() }),
+        None => Ok(()),
+    }
+}
+
+fn validate_token_info(
+    name: &str,
+    symbol: &str,
+    decimals: u8,
+) -> Result<(), ContractError> {
+    if !(MIN_NAME_LENGTH..=MAX_NAME_LENGTH).contains(&name.len()) {
+        return Err(ContractError::InvalidName {});
+    }
+    if !(MIN_SYMBOL_LENGTH..=MAX_SYMBOL_LENGTH).contains(&symbol.len())
+        || !symbol.bytes().all(|b| b.is_ascii_alphabetic() || b == b'-')
+    {
+        return Err(ContractError::InvalidSymbol {});
+    }
+    if decimals > MAX_DECIMALS {
+        return Err(ContractError::InvalidDecimals {});
+    }
+    Ok(())
+}
+
+fn check_batch_size(batch: &[Cw20Coin]) -> Result<(), ContractError> {
+    if batch.is_empty() {
+        return Err(ContractError::EmptyBatch {});
+    }
+    if batch.len() > MAX_BATCH_SIZE {
+        return Err(ContractError::BatchTooLarge { max: MAX_BATCH_SIZE });
+    }
+    Ok(())
+}
+
+fn create_accounts(
+    deps: &mut DepsMut,
+    env: &Env,
+    accounts: &[Cw20Coin],
+) -> Result<Uint128, ContractError> {
+    let mut addresses = accounts.iter().map(|c| c.address.as_str()).collect::<Vec<_>>();
+    addresses.sort_unstable();
+    addresses.dedup();
+    if addresses.len() != accounts.len() {
+        return Err(ContractError::DuplicateInitialBalanceAddresses {});
+    }
+
+    let mut total_supply = Uint128::zero();
+    for row in accounts {
+        let address = deps.api.addr_validate(&row.address)?;
+        BALANCES.save(deps.storage, &address, &row.amount, env.block.height)?;
+        total_supply += row.amount;
+    }
+
+    Ok(total_supply)
+}
+
+/// Writes the allowance under both keyings, a zero allowance is removed.
+fn save_allowance(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    spender: &Addr,
+    allowance: &AllowanceInfo,
+) -> StdResult<()> {
+    if allowance.allowance.is_zero() {
+        remove_allowance(storage, owner, spender);
+        return Ok(());
+    }
+    ALLOWANCES.save(storage, (owner, spender), allowance)?;
+    ALLOWANCES_SPENDER.save(storage, (spender, owner), allowance)
+}
+
+fn remove_allowance(storage: &mut dyn Storage, owner: &Addr, spender: &Addr) {
+    ALLOWANCES.remove(storage, (owner, spender));
+    ALLOWANCES_SPENDER.remove(storage, (spender, owner));
+}
+
+fn query_minters(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<MintersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|s| deps.api.addr_validate(&s))
+        .transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let minters = MINTERS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (address, mut quota) = item?;
+            quota.roll(env.block.time);
+            Ok(MinterQuotaResponse {
+                address: address.to_string(),
+                allowance: quota.allowance,
+                period: quota.period,
+                minted: quota.minted,
+                remaining: quota.remaining(),
+                period_start: quota.period_start,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(MintersResponse { minters })
+}
+
+fn query_download_logo(
+    deps: Deps,
+) -> StdResult<DownloadLogoResponse> {
+    match LOGO.may_load(deps.storage)? {
+        Some(Logo::Embedded(EmbeddedLogo::Svg(data))) => Ok(DownloadLogoResponse {
+            mime_type: "image/svg+xml".to_string(),
+            data,
+        }),
+        Some(Logo::Embedded(EmbeddedLogo::Png(data))) => Ok(DownloadLogoResponse {
+            mime_type: "image/png".to_string(),
+            data,
+        }),
+        _ => Err(StdError::not_found("logo")),
+    }
+}
+
+fn query_all_allowances(
+    deps: Deps,
+    env: Env,
+    owner: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AllAllowancesResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after.map(Addr::unchecked);
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let allowances = ALLOWANCES
+        .prefix(&owner)
+        .range(deps.storage, start, None, Order::Ascending)
+        .filter(|item| !matches!(item, Ok((_, a)) if a.expires.is_expired(&env.block)))
+        .take(limit)
+        .map(|item| {
+            item.map(|(spender, a)| AllowanceInfoResponse {
+                spender: spender.to_string(),
+                allowance: a.allowance,
+                expires: a.expires,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(AllAllowancesResponse { allowances })
+}
+
+fn query_all_spender_allowances(
+    deps: Deps,
+    env: Env,
+    spender: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AllSpenderAllowancesResponse> {
+    let spender = deps.api.addr_validate(&spender)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after.map(Addr::unchecked);
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let allowances = ALLOWANCES_SPENDER
+        .prefix(&spender)
+        .range(deps.storage, start, None, Order::Ascending)
+        .filter(|item| !matches!(item, Ok((_, a)) if a.expires.is_expired(&env.block)))
+        .take(limit)
+        .map(|item| {
+            item.map(|(owner, a)| SpenderAllowanceInfo {
+                owner: owner.to_string(),
+                allowance: a.allowance,
+                expires: a.expires,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(AllSpenderAllowancesResponse { allowances })
+}
+
+fn query_all_accounts(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AllAccountsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after.map(Addr::unchecked);
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let accounts = BALANCES
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(Into::into))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(AllAccountsResponse { accounts })
+}
+
+fn query_quote_transfer(
+    deps: Deps,
+    sender: String,
+    recipient: String,
+    amount: Uint128,
+) -> StdResult<QuoteTransferResponse> {
+    let sender = deps.api.addr_validate(&sender)?;
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let token_info = TOKEN_INFO.load(deps.storage)?;
+
+    let fee = transfer_fee(deps.storage, &token_info, &sender, &recipient, amount)?;
+    Ok(QuoteTransferResponse {
+        amount,
+        fee,
+        net_amount: amount - fee,
+    })
+}
+
+fn query_frozen_accounts(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<FrozenAccountsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after.map(Addr::unchecked);
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let accounts = FROZEN
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(Into::into))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(FrozenAccountsResponse { accounts })
 }
\ No newline at end of file
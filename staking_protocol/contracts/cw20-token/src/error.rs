@@ -1,17 +1,105 @@
-use cosmwasm_std::StdError;
-use thiserror::Error;
-
-#[derive(Error, Debug)]
-pub enum ContractError {
-    #[error("{0}")]
-    Std(#[from] StdError),
-
-    #[error("Unauthorized")]
-    Unauthorized {},
-
-    #[error("Insufficient balance")]
-    InsufficientBalance {},
-    
-    #[error("Insufficient allowance")]
-    InsufficientAllowance {},
+use cosmwasm_std::{StdError, Uint128};
+use cw_ownable::OwnershipError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Ownership(#[from] OwnershipError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Insufficient balance")]
+    InsufficientBalance {},
+    
+    #[error("Insufficient allowance")]
+    InsufficientAllowance {},
+
+    #[error("Allowance is expired")]
+    Expired {},
+
+    #[error("Invalid expiration value")]
+    InvalidExpiration {},
+
+    #[error("Cannot set allowance to own account")]
+    CannotSetOwnAccount {},
+
+    #[error("Name is not in the expected format (3-50 UTF-8 bytes)")]
+    InvalidName {},
+
+    #[error("Ticker symbol is not in expected format [a-zA-Z\\-]{{3,12}}")]
+    InvalidSymbol {},
+
+    #[error("Decimals must not exceed 18")]
+    InvalidDecimals {},
+
+    #[error("Minting cannot exceed the cap")]
+    CannotExceedCap {},
+
+    #[error("Mint quota exceeded, {remaining} left in this period")]
+    MintQuotaExceeded { remaining: Uint128 },
+
+    #[error("Mint period must be greater than zero")]
+    InvalidMintPeriod {},
+
+    #[error("Token has a fixed supply")]
+    MintingDisabled {},
+
+    #[error("Batch is empty")]
+    EmptyBatch {},
+
+    #[error("Batch exceeds the limit of {max} entries")]
+    BatchTooLarge { max: usize },
+
+    #[error("Duplicate initial balance addresses")]
+    DuplicateInitialBalanceAddresses {},
+
+    #[error("Logo binary data exceeds 5KB limit")]
+    LogoTooBig {},
+
+    #[error("Invalid xml preamble for SVG")]
+    InvalidXmlPreamble {},
+
+    #[error("Invalid png header")]
+    InvalidPngHeader {},
+
+    #[error("Permit has expired")]
+    PermitExpired {},
+
+    #[error("Invalid permit nonce, expected {expected}")]
+    InvalidNonce { expected: u64 },
+
+    #[error("Public key does not belong to the owner")]
+    PubkeyMismatch {},
+
+    #[error("Invalid permit signature")]
+    InvalidSignature {},
+
+    #[error("Compliance mode is not enabled for this token")]
+    ComplianceDisabled {},
+
+    #[error("Account {address} is frozen")]
+    AccountFrozen { address: String },
+
+    #[error("Token is paused")]
+    Paused {},
+
+    #[error("Transfer fee must be at most {max} bps and come with a fee recipient")]
+    InvalidTransferFee { max: u16 },
+
+    #[error("Cannot transfer tokens to the token contract")]
+    TransferToSelf {},
+
+    #[error("Given address already registered as a hook")]
+    HookAlreadyRegistered {},
+
+    #[error("Given address not registered as a hook")]
+    HookNotRegistered {},
+
+    #[error("Hooks exceed the limit of {max}")]
+    TooManyHooks { max: usize },
 }
\ No newline at end of file
@@ -1,6 +1,6 @@
-pub mod contract;
-pub mod error;
-pub mod msg;
-pub mod state;
-
+pub mod contract;
+pub mod error;
+pub mod msg;
+pub mod state;
+
 pub use crate::error::ContractError;
\ No newline at end of file
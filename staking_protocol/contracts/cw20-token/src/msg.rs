@@ -1,76 +1,465 @@
-use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Uint128;
-
-#[cw_serde]
-pub struct InstantiateMsg {
-    pub name: String,
-    pub symbol: String,
-    pub decimals: u8,
-    pub minter: Option<String>,
-}
-
-#[cw_serde]
-pub enum ExecuteMsg {
-    Transfer {
-        recipient: String,
-        amount: Uint128,
-    },
-    Burn {
-        amount: Uint128
-    },
-    Mint {
-        recipient: String,
-        amount: Uint128,
-    },
-    Approve {
-        spender: String,
-        amount: Uint128,
-    },
-    TransferFrom {
-        owner: String,
-        recipient: String,
-        amount: Uint128,
-    },
-    UpdateMinter {
-        new_minter: Option<String>,
-    },
-}
-
-#[derive(QueryResponses)]
-#[cw_serde]
-pub enum QueryMsg {
-    #[returns(BalanceResponse)]
-    Balance {address: String},
-
-    #[returns(AllowanceResponse)]
-    Allowance {owner: String, spender: String},
-
-    #[returns(TokenInfoResponse)]
-    TokenInfo {},
-
-    #[returns(MinterResponse)]
-    Minter {},
-}
-
-#[cw_serde]
-pub struct BalanceResponse {
-    pub balance: Uint128,
-}
-
-#[cw_serde]
-pub struct AllowanceResponse {
-    pub allowance: Uint128,
-}
-
-#[cw_serde]
-pub struct TokenInfoResponse {
-    pub name: String,
-    pub symbol: String,
-    pub decimals: u8,
-    pub total_supply: Uint128,
-}
-
-#[cw_serde]
-pub struct MinterResponse {
-    pub minter: Option<String>,
-}
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{to_json_binary, Binary, CosmosMsg, StdResult, Timestamp, Uint128, WasmMsg};
+use cw20::{Expiration, Logo};
+use cw_ownable::Action;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use shared::Cw20Coin;
+
+#[cw_serde]
+pub struct InstantiateMarketingInfo {
+    pub project: Option<String>,
+    pub description: Option<String>,
+    pub marketing: Option<String>,
+    pub logo: Option<Logo>,
+}
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub initial_balances: Vec<Cw20Coin>,
+    /// Manages the minter registry, defaults to the sender
+    pub owner: Option<String>,
+    /// Unrestricted minter, registry minters are bound by their quotas.
+    /// Without one only registry minters can mint, and once the registry
+    /// is empty too the supply is fixed
+    pub minter: Option<String>,
+    /// Upper bound on total supply, checked on every mint
+    pub cap: Option<Uint128>,
+    pub marketing: Option<InstantiateMarketingInfo>,
+    /// Lets the owner freeze accounts, can't be changed after instantiation
+    pub compliance: bool,
+    /// Fee on transfers in basis points, set together with `fee_recipient`
+    pub transfer_fee_bps: Option<u16>,
+    pub fee_recipient: Option<String>,
+}
+
+/// cw20-base's instantiate message, tooling written for cw20-base can
+/// deploy this contract unchanged. The sender becomes the owner and
+/// compliance mode and transfer fees stay off
+#[cw_serde]
+pub struct Cw20BaseInstantiateMsg {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub initial_balances: Vec<Cw20Coin>,
+    pub mint: Option<cw20::MinterResponse>,
+    pub marketing: Option<InstantiateMarketingInfo>,
+}
+
+impl From<Cw20BaseInstantiateMsg> for InstantiateMsg {
+    fn from(msg: Cw20BaseInstantiateMsg) -> Self {
+        let (minter, cap) = match msg.mint {
+            Some(mint) => (Some(mint.minter), mint.cap),
+            None => (None, None),
+        };
+        InstantiateMsg {
+            name: msg.name,
+            symbol: msg.symbol,
+            decimals: msg.decimals,
+            initial_balances: msg.initial_balances,
+            owner: None,
+            minter,
+            cap,
+            marketing: msg.marketing,
+            compliance: false,
+            transfer_fee_bps: None,
+            fee_recipient: None,
+        }
+    }
+}
+
+/// Either instantiate message, sent in its plain json form
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum InstantiateMode {
+    Token(InstantiateMsg),
+    Cw20Base(Cw20BaseInstantiateMsg),
+}
+
+impl From<InstantiateMode> for InstantiateMsg {
+    fn from(msg: InstantiateMode) -> Self {
+        match msg {
+            InstantiateMode::Token(msg) => msg,
+            InstantiateMode::Cw20Base(msg) => msg.into(),
+        }
+    }
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    Transfer {
+        recipient: String,
+        amount: Uint128,
+    },
+    /// Transfers to a contract and calls its Receive hook with `msg`,
+    /// the hook is told the amount net of transfer fees
+    Send {
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+    },
+    Burn {
+        amount: Uint128
+    },
+    Mint {
+        recipient: String,
+        amount: Uint128,
+    },
+    /// Up to 100 transfers from the sender, all or nothing
+    BatchTransfer {
+        transfers: Vec<Cw20Coin>,
+    },
+    /// Owner only airdrop, bound by the cap like a regular mint and only
+    /// available while the token has an unrestricted minter
+    BatchMint {
+        mints: Vec<Cw20Coin>,
+    },
+    /// Overwrites the allowance with a non-expiring one, prefer
+    /// IncreaseAllowance / DecreaseAllowance to avoid approve races
+    Approve {
+        spender: String,
+        amount: Uint128,
+    },
+    IncreaseAllowance {
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    },
+    DecreaseAllowance {
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    },
+    TransferFrom {
+        owner: String,
+        recipient: String,
+        amount: Uint128,
+    },
+    /// Send from `owner`'s balance using the sender's allowance
+    SendFrom {
+        owner: String,
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+    },
+    /// Burns from `owner`'s balance using the sender's allowance
+    BurnFrom {
+        owner: String,
+        amount: Uint128,
+    },
+    /// Sets `owner`'s allowance for `spender` from an ed25519 signature
+    /// over the JSON encoded `PermitPayload`, anyone can submit it
+    Permit {
+        owner: String,
+        spender: String,
+        amount: Uint128,
+        expiration: Timestamp,
+        nonce: u64,
+        signature: Binary,
+        pubkey: Binary,
+    },
+    UpdateMinter {
+        new_minter: Option<String>,
+    },
+    /// Registers a minter or replaces its quota, owner only
+    SetMinter {
+        minter: String,
+        allowance: Uint128,
+        period: u64,
+    },
+    /// Removing the last one from a token without an unrestricted minter
+    /// fixes its supply
+    RemoveMinter {
+        minter: String,
+    },
+    UpdateOwnership(Action),
+    /// Only callable by the marketing address, `Some("")` clears a field
+    UpdateMarketing {
+        project: Option<String>,
+        description: Option<String>,
+        marketing: Option<String>,
+    },
+    UploadLogo(Logo),
+    /// Owner only, passing no fee turns transfer fees off
+    UpdateTransferFee {
+        transfer_fee_bps: Option<u16>,
+        fee_recipient: Option<String>,
+    },
+    /// Owner only, transfers from or to an exempt address are fee free
+    UpdateFeeExemptions {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    /// Halts every balance change until unpaused, owner only
+    Pause {},
+    Unpause {},
+    /// Compliance mode only, blocks transfers and burns of the account
+    Freeze {
+        address: String,
+    },
+    Unfreeze {
+        address: String,
+    },
+    /// Compliance mode only, burns from any account without an allowance,
+    /// frozen ones included
+    ForceBurn {
+        address: String,
+        amount: Uint128,
+    },
+    /// Owner only, registers a contract that is sent a `TransferHookMsg`
+    /// after every transfer. A failing hook reverts the transfer
+    AddHook {
+        addr: String,
+    },
+    RemoveHook {
+        addr: String,
+    },
+}
+
+/// Sent to every registered hook once tokens moved between two accounts,
+/// `amount` is what `to` received after transfer fees
+#[cw_serde]
+pub struct TransferHookMsg {
+    pub from: String,
+    pub to: String,
+    pub amount: Uint128,
+}
+
+impl TransferHookMsg {
+    pub fn into_cosmos_msg(self, contract_addr: impl Into<String>) -> StdResult<CosmosMsg> {
+        let msg = to_json_binary(&TransferHookExecuteMsg::TransferHook(self))?;
+        Ok(WasmMsg::Execute {
+            contract_addr: contract_addr.into(),
+            msg,
+            funds: vec![],
+        }
+        .into())
+    }
+}
+
+/// The variant a hook contract adds to its own ExecuteMsg
+#[cw_serde]
+pub enum TransferHookExecuteMsg {
+    TransferHook(TransferHookMsg),
+}
+
+/// What the owner signs for a `Permit`, the chain id and contract
+/// address keep a signature from being replayed elsewhere
+#[cw_serde]
+pub struct PermitPayload {
+    pub chain_id: String,
+    pub contract: String,
+    pub owner: String,
+    pub spender: String,
+    pub amount: Uint128,
+    pub expiration: Timestamp,
+    pub nonce: u64,
+}
+
+#[derive(QueryResponses)]
+#[cw_serde]
+pub enum QueryMsg {
+    #[returns(BalanceResponse)]
+    Balance {address: String},
+
+    /// Balance at the start of the given block
+    #[returns(BalanceResponse)]
+    BalanceAtHeight {address: String, height: u64},
+
+    /// Total supply at the start of the given block
+    #[returns(TotalSupplyResponse)]
+    TotalSupplyAtHeight {height: u64},
+
+    #[returns(AllowanceResponse)]
+    Allowance {owner: String, spender: String},
+
+    /// Fee charged on a transfer and what the recipient receives
+    #[returns(QuoteTransferResponse)]
+    QuoteTransfer {sender: String, recipient: String, amount: Uint128},
+
+    #[returns(FeeExemptResponse)]
+    FeeExempt {address: String},
+
+    #[returns(PauseStatusResponse)]
+    PauseStatus {},
+
+    /// Whether the token was instantiated in compliance mode
+    #[returns(ComplianceStatusResponse)]
+    ComplianceStatus {},
+
+    #[returns(FrozenResponse)]
+    Frozen {address: String},
+
+    #[returns(FrozenAccountsResponse)]
+    FrozenAccounts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Nonce the owner's next permit has to carry
+    #[returns(NonceResponse)]
+    Nonce {owner: String},
+
+    #[returns(TokenInfoResponse)]
+    TokenInfo {},
+
+    #[returns(MinterResponse)]
+    Minter {},
+
+    #[returns(MintersResponse)]
+    Minters {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(HooksResponse)]
+    Hooks {},
+
+    #[returns(cw_ownable::Ownership<cosmwasm_std::Addr>)]
+    Ownership {},
+
+    #[returns(cw20::MarketingInfoResponse)]
+    MarketingInfo {},
+
+    #[returns(cw20::DownloadLogoResponse)]
+    DownloadLogo {},
+
+    #[returns(AllAllowancesResponse)]
+    AllAllowances {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(AllSpenderAllowancesResponse)]
+    AllSpenderAllowances {
+        spender: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(AllAccountsResponse)]
+    AllAccounts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct BalanceResponse {
+    pub balance: Uint128,
+}
+
+#[cw_serde]
+pub struct TotalSupplyResponse {
+    pub total_supply: Uint128,
+}
+
+#[cw_serde]
+pub struct AllowanceResponse {
+    pub allowance: Uint128,
+    pub expires: Expiration,
+}
+
+#[cw_serde]
+pub struct QuoteTransferResponse {
+    pub amount: Uint128,
+    pub fee: Uint128,
+    pub net_amount: Uint128,
+}
+
+#[cw_serde]
+pub struct FeeExemptResponse {
+    pub exempt: bool,
+}
+
+#[cw_serde]
+pub struct PauseStatusResponse {
+    pub paused: bool,
+}
+
+#[cw_serde]
+pub struct ComplianceStatusResponse {
+    pub compliance: bool,
+}
+
+#[cw_serde]
+pub struct FrozenResponse {
+    pub frozen: bool,
+}
+
+#[cw_serde]
+pub struct FrozenAccountsResponse {
+    pub accounts: Vec<String>,
+}
+
+#[cw_serde]
+pub struct NonceResponse {
+    pub nonce: u64,
+}
+
+#[cw_serde]
+pub struct TokenInfoResponse {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub total_supply: Uint128,
+}
+
+#[cw_serde]
+pub struct MinterResponse {
+    pub minter: Option<String>,
+    pub cap: Option<Uint128>,
+}
+
+#[cw_serde]
+pub struct MinterQuotaResponse {
+    pub address: String,
+    pub allowance: Uint128,
+    pub period: u64,
+    pub minted: Uint128,
+    pub remaining: Uint128,
+    pub period_start: Timestamp,
+}
+
+#[cw_serde]
+pub struct MintersResponse {
+    pub minters: Vec<MinterQuotaResponse>,
+}
+
+#[cw_serde]
+pub struct HooksResponse {
+    pub hooks: Vec<String>,
+}
+
+#[cw_serde]
+pub struct AllowanceInfo {
+    pub spender: String,
+    pub allowance: Uint128,
+    pub expires: Expiration,
+}
+
+#[cw_serde]
+pub struct AllAllowancesResponse {
+    pub allowances: Vec<AllowanceInfo>,
+}
+
+#[cw_serde]
+pub struct SpenderAllowanceInfo {
+    pub owner: String,
+    pub allowance: Uint128,
+    pub expires: Expiration,
+}
+
+#[cw_serde]
+pub struct AllSpenderAllowancesResponse {
+    pub allowances: Vec<SpenderAllowanceInfo>,
+}
+
+#[cw_serde]
+pub struct AllAccountsResponse {
+    pub accounts: Vec<String>,
+}
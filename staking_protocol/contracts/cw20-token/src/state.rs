@@ -1,16 +1,79 @@
-use cosmwasm_std::{Addr, Uint128};
-use cw_storage_plus::{Item, Map};
-use cosmwasm_schema::cw_serde;
-
-#[cw_serde]
-pub struct TokenInfo  {
-    pub name: String,
-    pub symbol: String,
-    pub decimals: u8,
-    pub total_supply: Uint128,
-    pub minter: Option<Addr>,
-}
-
-pub const TOKEN_INFO: Item<TokenInfo> = Item::new("token_info");
-pub const BALANCES: Map<&Addr, Uint128> = Map::new("balance");
-pub const ALLOWANCES: Map<(&Addr, &Addr), Uint128> = Map::new("allowance");
\ No newline at end of file
+use cosmwasm_std::{Addr, Empty, Timestamp, Uint128};
+use cw_storage_plus::{Item, Map, SnapshotItem, SnapshotMap, Strategy};
+use cosmwasm_schema::cw_serde;
+use cw20::{Expiration, Logo, MarketingInfoResponse};
+
+#[cw_serde]
+pub struct TokenInfo  {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub minter: Option<Addr>,
+    pub cap: Option<Uint128>,
+    pub compliance: bool,
+    pub transfer_fee_bps: Option<u16>,
+    pub fee_recipient: Option<Addr>,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct AllowanceInfo {
+    pub allowance: Uint128,
+    pub expires: Expiration,
+}
+
+/// Mint quota of a registered minter, `allowance` can be minted every
+/// `period` seconds
+#[cw_serde]
+pub struct MinterQuota {
+    pub allowance: Uint128,
+    pub period: u64,
+    pub minted: Uint128,
+    pub period_start: Timestamp,
+}
+
+impl MinterQuota {
+    /// Starts a new period once the current one is over
+    pub fn roll(&mut self, now: Timestamp) {
+        let elapsed = now.seconds().saturating_sub(self.period_start.seconds());
+        if elapsed >= self.period {
+            self.period_start = self.period_start.plus_seconds(elapsed - elapsed % self.period);
+            self.minted = Uint128::zero();
+        }
+    }
+
+    pub fn remaining(&self) -> Uint128 {
+        self.allowance.saturating_sub(self.minted)
+    }
+}
+
+pub const TOKEN_INFO: Item<TokenInfo> = Item::new("token_info");
+pub const MINTERS: Map<&Addr, MinterQuota> = Map::new("minters");
+pub const MARKETING_INFO: Item<MarketingInfoResponse> = Item::new("marketing_info");
+pub const LOGO: Item<Logo> = Item::new("logo");
+pub const PAUSED: Item<bool> = Item::new("paused");
+// balances and total supply keep a checkpoint per block for the
+// *AtHeight queries
+pub const BALANCES: SnapshotMap<&Addr, Uint128> = SnapshotMap::new(
+    "balance",
+    "balance__checkpoints",
+    "balance__changelog",
+    Strategy::EveryBlock,
+);
+pub const TOTAL_SUPPLY: SnapshotItem<Uint128> = SnapshotItem::new(
+    "total_supply",
+    "total_supply__checkpoints",
+    "total_supply__changelog",
+    Strategy::EveryBlock,
+);
+pub const ALLOWANCES: Map<(&Addr, &Addr), AllowanceInfo> = Map::new("allowance");
+// same allowances keyed (spender, owner) for enumeration by spender
+pub const ALLOWANCES_SPENDER: Map<(&Addr, &Addr), AllowanceInfo> = Map::new("allowance_spender");
+// next permit nonce per owner
+pub const NONCES: Map<&Addr, u64> = Map::new("nonces");
+// blocklist of frozen accounts, only ever filled in compliance mode
+pub const FROZEN: Map<&Addr, Empty> = Map::new("frozen");
+// addresses that neither pay nor cause transfer fees
+pub const FEE_EXEMPT: Map<&Addr, Empty> = Map::new("fee_exempt");
+// contracts told about every transfer, see TransferHookMsg
+pub const TRANSFER_HOOKS: Map<&Addr, Empty> = Map::new("transfer_hooks");
\ No newline at end of file
@@ -0,0 +1,1598 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::testing::MockApi;
+use cosmwasm_std::{
+    Addr, Api, Binary, CanonicalAddr, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError,
+    StdResult, Timestamp, Uint128, from_json, to_json_binary, to_json_vec,
+};
+use cw20::{
+    Cw20ReceiveMsg, DownloadLogoResponse, EmbeddedLogo, Expiration, Logo, LogoInfo,
+    MarketingInfoResponse,
+};
+use cw_multi_test::{App, ContractWrapper, Executor};
+use cw_storage_plus::Item;
+use ed25519_zebra::{SigningKey, VerificationKey};
+use sha2::{Digest, Sha256};
+
+use cw20_token::ContractError;
+use cw20_token::msg::{
+    AllAccountsResponse, AllAllowancesResponse, AllSpenderAllowancesResponse, AllowanceResponse,
+    BalanceResponse, ComplianceStatusResponse, Cw20BaseInstantiateMsg, ExecuteMsg, FeeExemptResponse,
+    FrozenAccountsResponse, HooksResponse, InstantiateMarketingInfo, InstantiateMode, InstantiateMsg,
+    MinterResponse, MintersResponse, NonceResponse, PauseStatusResponse, PermitPayload, QueryMsg,
+    QuoteTransferResponse, TokenInfoResponse, TotalSupplyResponse, TransferHookExecuteMsg,
+    TransferHookMsg,
+};
+use shared::Cw20Coin;
+
+use crate::test_utils::{instantiate_err, TestSetup};
+
+fn cw20_contract() -> Box<dyn cw_multi_test::Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw20_token::contract::execute,
+        cw20_token::contract::instantiate,
+        cw20_token::contract::query,
+    );
+    Box::new(contract)
+}
+
+// records every Cw20ReceiveMsg it gets, for the Send tests
+#[cw_serde]
+enum ReceiverExecuteMsg {
+    Receive(Cw20ReceiveMsg),
+}
+
+const RECEIVED: Item<Vec<Cw20ReceiveMsg>> = Item::new("received");
+
+fn receiver_contract() -> Box<dyn cw_multi_test::Contract<Empty>> {
+    fn instantiate(deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+        RECEIVED.save(deps.storage, &vec![])?;
+        Ok(Response::new())
+    }
+
+    fn execute(deps: DepsMut, _env: Env, _info: MessageInfo, msg: ReceiverExecuteMsg) -> StdResult<Response> {
+        let ReceiverExecuteMsg::Receive(msg) = msg;
+        RECEIVED.update(deps.storage, |mut received| -> StdResult<_> {
+            received.push(msg);
+            Ok(received)
+        })?;
+        Ok(Response::new())
+    }
+
+    fn query(deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        to_json_binary(&RECEIVED.load(deps.storage)?)
+    }
+
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+// records every transfer it is told about, or rejects them all
+const HOOKED: Item<Vec<TransferHookMsg>> = Item::new("hooked");
+
+fn hook_contract(reject: bool) -> Box<dyn cw_multi_test::Contract<Empty>> {
+    fn instantiate(deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+        HOOKED.save(deps.storage, &vec![])?;
+        Ok(Response::new())
+    }
+
+    fn recording(deps: DepsMut, _env: Env, _info: MessageInfo, msg: TransferHookExecuteMsg) -> StdResult<Response> {
+        let TransferHookExecuteMsg::TransferHook(transfer) = msg;
+        HOOKED.update(deps.storage, |mut hooked| -> StdResult<_> {
+            hooked.push(transfer);
+            Ok(hooked)
+        })?;
+        Ok(Response::new())
+    }
+
+    fn rejecting(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: TransferHookExecuteMsg) -> StdResult<Response> {
+        Err(StdError::generic_err("transfer rejected by hook"))
+    }
+
+    fn query(deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        to_json_binary(&HOOKED.load(deps.storage)?)
+    }
+
+    let execute = if reject { rejecting } else { recording };
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+mod test_utils {
+
+    use super::*;
+
+    pub struct TestSetup {
+        pub app: App,
+        pub token_addr: Addr,
+        pub owner: Addr,
+    }
+
+    impl TestSetup {
+        pub fn new() -> Self {
+            Self::instantiate(&[], None, false).unwrap()
+        }
+
+        pub fn instantiate(
+            initial_balances: &[(&str, u128)],
+            cap: Option<Uint128>,
+            compliance: bool,
+        ) -> Result<Self, ContractError> {
+            Self::instantiate_with(|msg, api| {
+                msg.initial_balances = initial_balances
+                    .iter()
+                    .map(|(name, amount)| Cw20Coin {
+                        address: api.addr_make(name).to_string(),
+                        amount: Uint128::from(*amount),
+                    })
+                    .collect();
+                msg.cap = cap;
+                msg.compliance = compliance;
+            })
+        }
+
+        /// Instantiates an ownable token with marketing info, `customize`
+        /// can adjust the message before it is sent
+        pub fn instantiate_with(
+            customize: impl FnOnce(&mut InstantiateMsg, &MockApi),
+        ) -> Result<Self, ContractError> {
+            let mut app = App::default();
+            let owner = app.api().addr_make("owner");
+            let mut msg = InstantiateMsg {
+                name: "Test Token".to_string(),
+                symbol: "TST".to_string(),
+                decimals: 18,
+                initial_balances: vec![],
+                owner: None,
+                minter: Some(owner.to_string()),
+                cap: None,
+                marketing: Some(InstantiateMarketingInfo {
+                    project: Some("Test Project".to_string()),
+                    description: None,
+                    marketing: Some(app.api().addr_make("marketing").to_string()),
+                    logo: None,
+                }),
+                compliance: false,
+                transfer_fee_bps: None,
+                fee_recipient: None,
+            };
+            customize(&mut msg, app.api());
+
+            let code_id = app.store_code(cw20_contract());
+            let token_addr = app
+                .instantiate_contract(code_id, owner.clone(), &msg, &[], "Test Token", None)
+                .map_err(|err| err.downcast::<ContractError>().unwrap())?;
+
+            Ok(TestSetup {
+                app,
+                token_addr,
+                owner,
+            })
+        }
+
+        pub fn addr(&self, name: &str) -> Addr {
+            self.app.api().addr_make(name)
+        }
+
+        pub fn execute(&mut self, sender: &Addr, msg: &ExecuteMsg) {
+            self.app
+                .execute_contract(sender.clone(), self.token_addr.clone(), msg, &[])
+                .unwrap();
+        }
+
+        pub fn execute_err(&mut self, sender: &Addr, msg: &ExecuteMsg) -> ContractError {
+            self.app
+                .execute_contract(sender.clone(), self.token_addr.clone(), msg, &[])
+                .unwrap_err()
+                .downcast()
+                .unwrap()
+        }
+
+        pub fn mint(&mut self, recipient: &Addr, amount: u128) {
+            let owner = self.owner.clone();
+            self.execute(&owner, &ExecuteMsg::Mint {
+                recipient: recipient.to_string(),
+                amount: Uint128::from(amount),
+            });
+        }
+
+        pub fn balance(&self, address: &Addr) -> Uint128 {
+            let res: BalanceResponse = self.app
+                .wrap()
+                .query_wasm_smart(&self.token_addr, &QueryMsg::Balance {
+                    address: address.to_string(),
+                })
+                .unwrap();
+            res.balance
+        }
+
+        pub fn allowance(&self, owner: &Addr, spender: &Addr) -> AllowanceResponse {
+            self.app
+                .wrap()
+                .query_wasm_smart(&self.token_addr, &QueryMsg::Allowance {
+                    owner: owner.to_string(),
+                    spender: spender.to_string(),
+                })
+                .unwrap()
+        }
+
+        pub fn advance_blocks(&mut self, blocks: u64) {
+            self.app.update_block(|block| {
+                block.height += blocks;
+                block.time = block.time.plus_seconds(5 * blocks);
+            });
+        }
+    }
+
+    pub fn instantiate_err(name: &str, symbol: &str, decimals: u8) -> ContractError {
+        TestSetup::instantiate_with(|msg, _| {
+            msg.name = name.to_string();
+            msg.symbol = symbol.to_string();
+            msg.decimals = decimals;
+        })
+        .err()
+        .unwrap()
+    }
+}
+
+#[test]
+fn test_increase_and_decrease_allowance() {
+    let mut setup = TestSetup::new();
+    let user1 = setup.addr("user1");
+    let user2 = setup.addr("user2");
+    let spender = setup.addr("spender");
+    setup.mint(&user1, 1000);
+
+    setup.execute(&user1, &ExecuteMsg::IncreaseAllowance {
+        spender: spender.to_string(),
+        amount: Uint128::from(300u128),
+        expires: None,
+    });
+    setup.execute(&user1, &ExecuteMsg::IncreaseAllowance {
+        spender: spender.to_string(),
+        amount: Uint128::from(200u128),
+        expires: None,
+    });
+    assert_eq!(setup.allowance(&user1, &spender).allowance, Uint128::from(500u128));
+
+    setup.execute(&user1, &ExecuteMsg::DecreaseAllowance {
+        spender: spender.to_string(),
+        amount: Uint128::from(100u128),
+        expires: None,
+    });
+    assert_eq!(setup.allowance(&user1, &spender).allowance, Uint128::from(400u128));
+
+    setup.execute(&spender, &ExecuteMsg::TransferFrom {
+        owner: user1.to_string(),
+        recipient: user2.to_string(),
+        amount: Uint128::from(150u128),
+    });
+    assert_eq!(setup.balance(&user2), Uint128::from(150u128));
+    assert_eq!(setup.allowance(&user1, &spender).allowance, Uint128::from(250u128));
+
+    // decreasing below zero clears the allowance
+    setup.execute(&user1, &ExecuteMsg::DecreaseAllowance {
+        spender: spender.to_string(),
+        amount: Uint128::from(1000u128),
+        expires: None,
+    });
+    assert_eq!(setup.allowance(&user1, &spender).allowance, Uint128::zero());
+
+    let err = setup.execute_err(&user1, &ExecuteMsg::IncreaseAllowance {
+        spender: user1.to_string(),
+        amount: Uint128::from(1u128),
+        expires: None,
+    });
+    assert_eq!(err.to_string(), ContractError::CannotSetOwnAccount {}.to_string());
+}
+
+#[test]
+fn test_allowance_expiration() {
+    let mut setup = TestSetup::new();
+    let user1 = setup.addr("user1");
+    let user2 = setup.addr("user2");
+    let spender = setup.addr("spender");
+    setup.mint(&user1, 1000);
+    let height = setup.app.block_info().height;
+
+    let err = setup.execute_err(&user1, &ExecuteMsg::IncreaseAllowance {
+        spender: spender.to_string(),
+        amount: Uint128::from(100u128),
+        expires: Some(Expiration::AtHeight(height)),
+    });
+    assert_eq!(err.to_string(), ContractError::InvalidExpiration {}.to_string());
+
+    setup.execute(&user1, &ExecuteMsg::IncreaseAllowance {
+        spender: spender.to_string(),
+        amount: Uint128::from(100u128),
+        expires: Some(Expiration::AtHeight(height + 10)),
+    });
+    let allowance = setup.allowance(&user1, &spender);
+    assert_eq!(allowance.allowance, Uint128::from(100u128));
+    assert_eq!(allowance.expires, Expiration::AtHeight(height + 10));
+
+    setup.advance_blocks(10);
+    assert_eq!(setup.allowance(&user1, &spender).allowance, Uint128::zero());
+
+    let err = setup.execute_err(&spender, &ExecuteMsg::TransferFrom {
+        owner: user1.to_string(),
+        recipient: user2.to_string(),
+        amount: Uint128::from(1u128),
+    });
+    assert_eq!(err.to_string(), ContractError::Expired {}.to_string());
+
+    // increasing an expired allowance starts again from zero
+    setup.execute(&user1, &ExecuteMsg::IncreaseAllowance {
+        spender: spender.to_string(),
+        amount: Uint128::from(50u128),
+        expires: Some(Expiration::Never {}),
+    });
+    assert_eq!(setup.allowance(&user1, &spender).allowance, Uint128::from(50u128));
+}
+
+#[test]
+fn test_enumerable_queries() {
+    let mut setup = TestSetup::new();
+    let user1 = setup.addr("user1");
+    let user2 = setup.addr("user2");
+    let user3 = setup.addr("user3");
+    let spender1 = setup.addr("spender1");
+    let spender2 = setup.addr("spender2");
+    setup.mint(&user1, 100);
+    setup.mint(&user2, 100);
+    setup.mint(&user3, 100);
+
+    for spender in [&spender1, &spender2] {
+        setup.execute(&user1, &ExecuteMsg::IncreaseAllowance {
+            spender: spender.to_string(),
+            amount: Uint128::from(10u128),
+            expires: None,
+        });
+    }
+    setup.execute(&user2, &ExecuteMsg::Approve {
+        spender: spender1.to_string(),
+        amount: Uint128::from(20u128),
+    });
+
+    let mut spenders = vec![spender1.to_string(), spender2.to_string()];
+    spenders.sort();
+    let res: AllAllowancesResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.token_addr, &QueryMsg::AllAllowances {
+            owner: user1.to_string(),
+            start_after: None,
+            limit: None,
+        })
+        .unwrap();
+    let listed: Vec<_> = res.allowances.iter().map(|a| a.spender.clone()).collect();
+    assert_eq!(listed, spenders);
+
+    let res: AllAllowancesResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.token_addr, &QueryMsg::AllAllowances {
+            owner: user1.to_string(),
+            start_after: Some(spenders[0].clone()),
+            limit: Some(1),
+        })
+        .unwrap();
+    assert_eq!(res.allowances.len(), 1);
+    assert_eq!(res.allowances[0].spender, spenders[1]);
+
+    let res: AllSpenderAllowancesResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.token_addr, &QueryMsg::AllSpenderAllowances {
+            spender: spender1.to_string(),
+            start_after: None,
+            limit: None,
+        })
+        .unwrap();
+    let mut owners: Vec<_> = res.allowances.iter().map(|a| (a.owner.clone(), a.allowance.u128())).collect();
+    owners.sort();
+    let mut expected = vec![(user1.to_string(), 10), (user2.to_string(), 20)];
+    expected.sort();
+    assert_eq!(owners, expected);
+
+    // a fully spent allowance drops out of both listings
+    setup.execute(&spender1, &ExecuteMsg::TransferFrom {
+        owner: user2.to_string(),
+        recipient: spender1.to_string(),
+        amount: Uint128::from(20u128),
+    });
+    let res: AllSpenderAllowancesResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.token_addr, &QueryMsg::AllSpenderAllowances {
+            spender: spender1.to_string(),
+            start_after: None,
+            limit: None,
+        })
+        .unwrap();
+    assert_eq!(res.allowances.len(), 1);
+    assert_eq!(res.allowances[0].owner, user1.to_string());
+
+    let mut accounts = [user1.to_string(), user2.to_string(), user3.to_string(), spender1.to_string()];
+    accounts.sort();
+    let res: AllAccountsResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.token_addr, &QueryMsg::AllAccounts {
+            start_after: Some(accounts[0].clone()),
+            limit: None,
+        })
+        .unwrap();
+    assert_eq!(res.accounts, accounts[1..]);
+}
+
+#[test]
+fn test_initial_balances_and_cap() {
+    let err = TestSetup::instantiate(&[("user1", 600), ("user2", 500)], Some(Uint128::from(1000u128)), false)
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), ContractError::CannotExceedCap {}.to_string());
+
+    let err = TestSetup::instantiate(&[("user1", 100), ("user1", 200)], None, false)
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), ContractError::DuplicateInitialBalanceAddresses {}.to_string());
+
+    let mut setup = TestSetup::instantiate(&[("user1", 600), ("user2", 300)], Some(Uint128::from(1000u128)), false)
+        .unwrap();
+    let owner = setup.owner.clone();
+    let user1 = setup.addr("user1");
+    let user2 = setup.addr("user2");
+    let user3 = setup.addr("user3");
+    assert_eq!(setup.balance(&user1), Uint128::from(600u128));
+    assert_eq!(setup.balance(&user2), Uint128::from(300u128));
+
+    let info: TokenInfoResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.token_addr, &QueryMsg::TokenInfo {})
+        .unwrap();
+    assert_eq!(info.total_supply, Uint128::from(900u128));
+
+    let minter: MinterResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.token_addr, &QueryMsg::Minter {})
+        .unwrap();
+    assert_eq!(minter.cap, Some(Uint128::from(1000u128)));
+
+    // minting up to the cap is fine, one more unit is not
+    setup.mint(&user3, 100);
+    let err = setup.execute_err(&owner, &ExecuteMsg::Mint {
+        recipient: user3.to_string(),
+        amount: Uint128::one(),
+    });
+    assert_eq!(err.to_string(), ContractError::CannotExceedCap {}.to_string());
+
+    // burning frees room under the cap again
+    setup.execute(&user1, &ExecuteMsg::Burn { amount: Uint128::from(50u128) });
+    setup.mint(&user3, 50);
+    assert_eq!(setup.balance(&user3), Uint128::from(150u128));
+}
+
+#[test]
+fn test_ownable_and_minter_modes() {
+    // without a minter the supply is fixed, the owner can't mint either
+    let mut setup = TestSetup::instantiate_with(|msg, api| {
+        msg.minter = None;
+        msg.initial_balances = vec![Cw20Coin {
+            address: api.addr_make("user1").to_string(),
+            amount: Uint128::from(100u128),
+        }];
+    })
+    .unwrap();
+    let owner = setup.owner.clone();
+    let user1 = setup.addr("user1");
+    let minter: MinterResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.token_addr, &QueryMsg::Minter {})
+        .unwrap();
+    assert_eq!(minter.minter, None);
+
+    let err = setup.execute_err(&owner, &ExecuteMsg::Mint {
+        recipient: owner.to_string(),
+        amount: Uint128::one(),
+    });
+    assert_eq!(err.to_string(), ContractError::MintingDisabled {}.to_string());
+    let err = setup.execute_err(&owner, &ExecuteMsg::BatchMint {
+        mints: vec![Cw20Coin { address: owner.to_string(), amount: Uint128::one() }],
+    });
+    assert_eq!(err.to_string(), ContractError::MintingDisabled {}.to_string());
+    let err = setup.execute_err(&owner, &ExecuteMsg::SetMinter {
+        minter: owner.to_string(),
+        allowance: Uint128::from(100u128),
+        period: 3600,
+    });
+    assert_eq!(err.to_string(), ContractError::MintingDisabled {}.to_string());
+    assert_eq!(setup.balance(&user1), Uint128::from(100u128));
+
+    // the owner mints when it is the minter
+    let mut setup = TestSetup::new();
+    let owner = setup.owner.clone();
+    let user1 = setup.addr("user1");
+    let minter: MinterResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.token_addr, &QueryMsg::Minter {})
+        .unwrap();
+    assert_eq!(minter.minter, Some(owner.to_string()));
+    setup.mint(&user1, 100);
+
+    let err = setup.execute_err(&user1, &ExecuteMsg::Mint {
+        recipient: user1.to_string(),
+        amount: Uint128::one(),
+    });
+    assert_eq!(err.to_string(), ContractError::Unauthorized {}.to_string());
+
+    // a dedicated minter takes over from the owner
+    let mut setup = TestSetup::instantiate_with(|msg, api| {
+        msg.minter = Some(api.addr_make("pool").to_string());
+    })
+    .unwrap();
+    let owner = setup.owner.clone();
+    let pool = setup.addr("pool");
+    let user1 = setup.addr("user1");
+    let minter: MinterResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.token_addr, &QueryMsg::Minter {})
+        .unwrap();
+    assert_eq!(minter.minter, Some(pool.to_string()));
+
+    let mint = ExecuteMsg::Mint {
+        recipient: user1.to_string(),
+        amount: Uint128::from(100u128),
+    };
+    let err = setup.execute_err(&owner, &mint);
+    assert_eq!(err.to_string(), ContractError::Unauthorized {}.to_string());
+    setup.execute(&pool, &mint);
+    assert_eq!(setup.balance(&user1), Uint128::from(100u128));
+}
+
+#[test]
+fn test_marketing_and_logo() {
+    let mut setup = TestSetup::new();
+    let owner = setup.owner.clone();
+    let marketing = setup.addr("marketing");
+
+    let info: MarketingInfoResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.token_addr, &QueryMsg::MarketingInfo {})
+        .unwrap();
+    assert_eq!(info.project, Some("Test Project".to_string()));
+    assert_eq!(info.marketing, Some(marketing.clone()));
+
+    let err = setup.execute_err(&owner, &ExecuteMsg::UpdateMarketing {
+        project: None,
+        description: Some("Staking token".to_string()),
+        marketing: None,
+    });
+    assert_eq!(err.to_string(), ContractError::Unauthorized {}.to_string());
+
+    setup.execute(&marketing, &ExecuteMsg::UpdateMarketing {
+        project: Some("".to_string()),
+        description: Some("Staking token".to_string()),
+        marketing: None,
+    });
+    let info: MarketingInfoResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.token_addr, &QueryMsg::MarketingInfo {})
+        .unwrap();
+    assert_eq!(info.project, None);
+    assert_eq!(info.description, Some("Staking token".to_string()));
+
+    // embedded logos are checked for format and size
+    let err = setup.execute_err(&marketing, &ExecuteMsg::UploadLogo(
+        Logo::Embedded(EmbeddedLogo::Png(Binary::from(b"not a png".to_vec()))),
+    ));
+    assert_eq!(err.to_string(), ContractError::InvalidPngHeader {}.to_string());
+
+    let err = setup.execute_err(&marketing, &ExecuteMsg::UploadLogo(
+        Logo::Embedded(EmbeddedLogo::Svg(Binary::from(b"<html></html>".to_vec()))),
+    ));
+    assert_eq!(err.to_string(), ContractError::InvalidXmlPreamble {}.to_string());
+
+    let mut big_svg = b"<svg ".to_vec();
+    big_svg.resize(6 * 1024, b' ');
+    let err = setup.execute_err(&marketing, &ExecuteMsg::UploadLogo(
+        Logo::Embedded(EmbeddedLogo::Svg(Binary::from(big_svg))),
+    ));
+    assert_eq!(err.to_string(), ContractError::LogoTooBig {}.to_string());
+
+    let svg = b"<?xml version=\"1.0\"?><svg></svg>".to_vec();
+    setup.execute(&marketing, &ExecuteMsg::UploadLogo(
+        Logo::Embedded(EmbeddedLogo::Svg(Binary::from(svg.clone()))),
+    ));
+    let info: MarketingInfoResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.token_addr, &QueryMsg::MarketingInfo {})
+        .unwrap();
+    assert_eq!(info.logo, Some(LogoInfo::Embedded));
+    let logo: DownloadLogoResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.token_addr, &QueryMsg::DownloadLogo {})
+        .unwrap();
+    assert_eq!(logo.mime_type, "image/svg+xml");
+    assert_eq!(logo.data, Binary::from(svg));
+
+    // a url logo has nothing to download
+    setup.execute(&marketing, &ExecuteMsg::UploadLogo(
+        Logo::Url("https://example.com/logo.png".to_string()),
+    ));
+    let res: Result<DownloadLogoResponse, _> = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.token_addr, &QueryMsg::DownloadLogo {});
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_instantiate_validation() {
+    let cases = [
+        ("TT", "TST", 6, ContractError::InvalidName {}),
+        (&"T".repeat(51), "TST", 6, ContractError::InvalidName {}),
+        ("Test Token", "TS", 6, ContractError::InvalidSymbol {}),
+        ("Test Token", "TOOLONGSYMBOL", 6, ContractError::InvalidSymbol {}),
+        ("Test Token", "TST1", 6, ContractError::InvalidSymbol {}),
+        ("Test Token", "T ST", 6, ContractError::InvalidSymbol {}),
+        ("Test Token", "TST", 19, ContractError::InvalidDecimals {}),
+        ("Test Token", "TST", 255, ContractError::InvalidDecimals {}),
+    ];
+
+    for (name, symbol, decimals, expected) in cases {
+        let err = instantiate_err(name, symbol, decimals);
+        assert_eq!(err.to_string(), expected.to_string(), "{name} / {symbol} / {decimals}");
+    }
+}
+
+#[test]
+fn test_minter_quotas() {
+    let mut setup = TestSetup::new();
+    let owner = setup.owner.clone();
+    let pool1 = setup.addr("pool1");
+    let pool2 = setup.addr("pool2");
+    let user1 = setup.addr("user1");
+
+    let set_pool = ExecuteMsg::SetMinter {
+        minter: pool1.to_string(),
+        allowance: Uint128::from(100u128),
+        period: 3600,
+    };
+    let err = setup.execute_err(&pool1, &set_pool);
+    assert_eq!(err.to_string(), ContractError::Ownership(cw_ownable::OwnershipError::NotOwner).to_string());
+
+    let err = setup.execute_err(&owner, &ExecuteMsg::SetMinter {
+        minter: pool1.to_string(),
+        allowance: Uint128::from(100u128),
+        period: 0,
+    });
+    assert_eq!(err.to_string(), ContractError::InvalidMintPeriod {}.to_string());
+
+    setup.execute(&owner, &set_pool);
+
+    let mint = |amount: u128| ExecuteMsg::Mint {
+        recipient: user1.to_string(),
+        amount: Uint128::from(amount),
+    };
+    setup.execute(&pool1, &mint(60));
+    let err = setup.execute_err(&pool1, &mint(50));
+    assert_eq!(
+        err.to_string(),
+        ContractError::MintQuotaExceeded { remaining: Uint128::from(40u128) }.to_string(),
+    );
+    setup.execute(&pool1, &mint(40));
+
+    // unregistered addresses can't mint at all
+    let err = setup.execute_err(&pool2, &mint(1));
+    assert_eq!(err.to_string(), ContractError::Unauthorized {}.to_string());
+
+    // 720 blocks of 5s is one full period, the quota resets
+    setup.advance_blocks(720);
+    let res: MintersResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.token_addr, &QueryMsg::Minters {
+            start_after: None,
+            limit: None,
+        })
+        .unwrap();
+    assert_eq!(res.minters.len(), 1);
+    assert_eq!(res.minters[0].address, pool1.to_string());
+    assert_eq!(res.minters[0].minted, Uint128::zero());
+    assert_eq!(res.minters[0].remaining, Uint128::from(100u128));
+
+    setup.execute(&pool1, &mint(100));
+    assert_eq!(setup.balance(&user1), Uint128::from(200u128));
+
+    setup.execute(&owner, &ExecuteMsg::RemoveMinter { minter: pool1.to_string() });
+    setup.advance_blocks(720);
+    let err = setup.execute_err(&pool1, &mint(1));
+    assert_eq!(err.to_string(), ContractError::Unauthorized {}.to_string());
+}
+
+#[test]
+fn test_quota_only_minting() {
+    let mut setup = TestSetup::new();
+    let owner = setup.owner.clone();
+    let pool1 = setup.addr("pool1");
+    let pool2 = setup.addr("pool2");
+    let user1 = setup.addr("user1");
+
+    // register the pool, then give up the unrestricted minter
+    setup.execute(&owner, &ExecuteMsg::SetMinter {
+        minter: pool1.to_string(),
+        allowance: Uint128::from(100u128),
+        period: 3600,
+    });
+    setup.execute(&owner, &ExecuteMsg::UpdateMinter { new_minter: None });
+
+    let mint = |amount: u128| ExecuteMsg::Mint {
+        recipient: user1.to_string(),
+        amount: Uint128::from(amount),
+    };
+    setup.execute(&pool1, &mint(100));
+    let err = setup.execute_err(&pool1, &mint(1));
+    assert_eq!(
+        err.to_string(),
+        ContractError::MintQuotaExceeded { remaining: Uint128::zero() }.to_string(),
+    );
+
+    // nobody mints without a quota, the owner included
+    let err = setup.execute_err(&pool2, &mint(1));
+    assert_eq!(err.to_string(), ContractError::Unauthorized {}.to_string());
+    let err = setup.execute_err(&owner, &mint(1));
+    assert_eq!(err.to_string(), ContractError::Unauthorized {}.to_string());
+    let err = setup.execute_err(&owner, &ExecuteMsg::BatchMint {
+        mints: vec![Cw20Coin { address: user1.to_string(), amount: Uint128::one() }],
+    });
+    assert_eq!(err.to_string(), ContractError::MintingDisabled {}.to_string());
+
+    // more quota minters can join while the registry is in use
+    setup.execute(&owner, &ExecuteMsg::SetMinter {
+        minter: pool2.to_string(),
+        allowance: Uint128::from(50u128),
+        period: 3600,
+    });
+    setup.execute(&pool2, &mint(50));
+    assert_eq!(setup.balance(&user1), Uint128::from(150u128));
+
+    // with the registry empty the supply is fixed for good
+    setup.execute(&owner, &ExecuteMsg::RemoveMinter { minter: pool1.to_string() });
+    setup.execute(&owner, &ExecuteMsg::RemoveMinter { minter: pool2.to_string() });
+    let err = setup.execute_err(&pool1, &mint(1));
+    assert_eq!(err.to_string(), ContractError::MintingDisabled {}.to_string());
+    let err = setup.execute_err(&owner, &ExecuteMsg::SetMinter {
+        minter: pool1.to_string(),
+        allowance: Uint128::from(100u128),
+        period: 3600,
+    });
+    assert_eq!(err.to_string(), ContractError::MintingDisabled {}.to_string());
+}
+
+#[test]
+fn test_balance_snapshots() {
+    let mut setup = TestSetup::new();
+    let user1 = setup.addr("user1");
+    let user2 = setup.addr("user2");
+    let start = setup.app.block_info().height;
+    setup.mint(&user1, 1000);
+
+    setup.advance_blocks(1);
+    setup.execute(&user1, &ExecuteMsg::Transfer {
+        recipient: user2.to_string(),
+        amount: Uint128::from(300u128),
+    });
+
+    setup.advance_blocks(1);
+    setup.execute(&user2, &ExecuteMsg::Burn { amount: Uint128::from(100u128) });
+    setup.advance_blocks(1);
+
+    let balance_at = |setup: &TestSetup, address: &Addr, height: u64| -> u128 {
+        let res: BalanceResponse = setup.app
+            .wrap()
+            .query_wasm_smart(&setup.token_addr, &QueryMsg::BalanceAtHeight {
+                address: address.to_string(),
+                height,
+            })
+            .unwrap();
+        res.balance.u128()
+    };
+    let supply_at = |setup: &TestSetup, height: u64| -> u128 {
+        let res: TotalSupplyResponse = setup.app
+            .wrap()
+            .query_wasm_smart(&setup.token_addr, &QueryMsg::TotalSupplyAtHeight { height })
+            .unwrap();
+        res.total_supply.u128()
+    };
+
+    // a snapshot reflects the state at the start of the block
+    assert_eq!(balance_at(&setup, &user1, start), 0);
+    assert_eq!(supply_at(&setup, start), 0);
+
+    assert_eq!(balance_at(&setup, &user1, start + 1), 1000);
+    assert_eq!(balance_at(&setup, &user2, start + 1), 0);
+    assert_eq!(supply_at(&setup, start + 1), 1000);
+
+    assert_eq!(balance_at(&setup, &user1, start + 2), 700);
+    assert_eq!(balance_at(&setup, &user2, start + 2), 300);
+    assert_eq!(supply_at(&setup, start + 2), 1000);
+
+    assert_eq!(balance_at(&setup, &user2, start + 3), 200);
+    assert_eq!(supply_at(&setup, start + 3), 900);
+
+    assert_eq!(setup.balance(&user2), Uint128::from(200u128));
+}
+
+#[test]
+fn test_batch_transfer_and_mint() {
+    let mut setup = TestSetup::new();
+    let owner = setup.owner.clone();
+    let user1 = setup.addr("user1");
+    let user2 = setup.addr("user2");
+    let user3 = setup.addr("user3");
+    let user4 = setup.addr("user4");
+    let coins = |entries: &[(&Addr, u128)]| -> Vec<Cw20Coin> {
+        entries
+            .iter()
+            .map(|(address, amount)| Cw20Coin {
+                address: address.to_string(),
+                amount: Uint128::from(*amount),
+            })
+            .collect()
+    };
+
+    let err = setup.execute_err(&user1, &ExecuteMsg::BatchMint {
+        mints: coins(&[(&user1, 500)]),
+    });
+    assert_eq!(err.to_string(), ContractError::Ownership(cw_ownable::OwnershipError::NotOwner).to_string());
+
+    setup.execute(&owner, &ExecuteMsg::BatchMint {
+        mints: coins(&[(&user1, 500), (&user2, 200)]),
+    });
+    assert_eq!(setup.balance(&user1), Uint128::from(500u128));
+    assert_eq!(setup.balance(&user2), Uint128::from(200u128));
+
+    let err = setup.execute_err(&user1, &ExecuteMsg::BatchTransfer { transfers: vec![] });
+    assert_eq!(err.to_string(), ContractError::EmptyBatch {}.to_string());
+
+    let too_many = vec![(&user3, 1); 101];
+    let err = setup.execute_err(&user1, &ExecuteMsg::BatchTransfer { transfers: coins(&too_many) });
+    assert_eq!(err.to_string(), ContractError::BatchTooLarge { max: 100 }.to_string());
+
+    // the whole batch fails when the sum exceeds the balance
+    let err = setup.execute_err(&user1, &ExecuteMsg::BatchTransfer {
+        transfers: coins(&[(&user3, 300), (&user4, 300)]),
+    });
+    assert_eq!(err.to_string(), ContractError::InsufficientBalance {}.to_string());
+    assert_eq!(setup.balance(&user3), Uint128::zero());
+
+    // same error as a single transfer
+    let err = setup.execute_err(&user1, &ExecuteMsg::Transfer {
+        recipient: user3.to_string(),
+        amount: Uint128::from(600u128),
+    });
+    assert_eq!(err.to_string(), ContractError::InsufficientBalance {}.to_string());
+
+    let res = setup.app
+        .execute_contract(
+            user1.clone(),
+            setup.token_addr.clone(),
+            &ExecuteMsg::BatchTransfer {
+                transfers: coins(&[(&user3, 300), (&user4, 150)]),
+            },
+            &[],
+        )
+        .unwrap();
+    let wasm = res.events.iter().find(|e| e.ty == "wasm").unwrap();
+    assert!(wasm.attributes.iter().any(|a| a.key == "count" && a.value == "2"));
+    assert!(wasm.attributes.iter().any(|a| a.key == "amount" && a.value == "450"));
+
+    assert_eq!(setup.balance(&user1), Uint128::from(50u128));
+    assert_eq!(setup.balance(&user3), Uint128::from(300u128));
+    assert_eq!(setup.balance(&user4), Uint128::from(150u128));
+}
+
+/// Signs a permit the way a wallet would, returns the owner address
+/// derived from the key together with the message
+fn signed_permit(
+    setup: &TestSetup,
+    key: &SigningKey,
+    spender: &Addr,
+    amount: u128,
+    expiration: Timestamp,
+    nonce: u64,
+) -> (Addr, ExecuteMsg) {
+    let pubkey = VerificationKey::from(key);
+    let hash = Sha256::digest(pubkey.as_ref());
+    let owner = setup.app.api().addr_humanize(&CanonicalAddr::from(&hash[..20])).unwrap();
+
+    let payload = PermitPayload {
+        chain_id: setup.app.block_info().chain_id,
+        contract: setup.token_addr.to_string(),
+        owner: owner.to_string(),
+        spender: spender.to_string(),
+        amount: Uint128::from(amount),
+        expiration,
+        nonce,
+    };
+    let signature = key.sign(&to_json_vec(&payload).unwrap());
+
+    let msg = ExecuteMsg::Permit {
+        owner: owner.to_string(),
+        spender: spender.to_string(),
+        amount: Uint128::from(amount),
+        expiration,
+        nonce,
+        signature: Binary::from(<[u8; 64]>::from(signature).to_vec()),
+        pubkey: Binary::from(pubkey.as_ref().to_vec()),
+    };
+    (owner, msg)
+}
+
+#[test]
+fn test_permit() {
+    let mut setup = TestSetup::new();
+    let key = SigningKey::from([7u8; 32]);
+    let spender = setup.addr("spender");
+    let relayer = setup.addr("relayer");
+    let expiration = setup.app.block_info().time.plus_seconds(600);
+
+    let (owner, permit) = signed_permit(&setup, &key, &spender, 250, expiration, 0);
+
+    // the relayer pays the gas, the owner never sends a transaction
+    setup.execute(&relayer, &permit);
+    let allowance = setup.allowance(&owner, &spender);
+    assert_eq!(allowance.allowance, Uint128::from(250u128));
+    assert_eq!(allowance.expires, Expiration::Never {});
+
+    let nonce: NonceResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.token_addr, &QueryMsg::Nonce { owner: owner.to_string() })
+        .unwrap();
+    assert_eq!(nonce.nonce, 1);
+
+    let err = setup.execute_err(&relayer, &permit);
+    assert_eq!(err.to_string(), ContractError::InvalidNonce { expected: 1 }.to_string());
+
+    // a signature over other terms doesn't verify
+    let (_, permit) = signed_permit(&setup, &key, &spender, 250, expiration, 1);
+    let ExecuteMsg::Permit { owner: permit_owner, spender: permit_spender, expiration, nonce, signature, pubkey, .. } = permit else {
+        unreachable!()
+    };
+    let tampered = ExecuteMsg::Permit {
+        owner: permit_owner,
+        spender: permit_spender,
+        amount: Uint128::from(1_000_000u128),
+        expiration,
+        nonce,
+        signature,
+        pubkey,
+    };
+    let err = setup.execute_err(&relayer, &tampered);
+    assert_eq!(err.to_string(), ContractError::InvalidSignature {}.to_string());
+
+    // a key can only sign for its own address
+    let other_key = SigningKey::from([8u8; 32]);
+    let (_, ExecuteMsg::Permit { pubkey, signature, .. }) =
+        signed_permit(&setup, &other_key, &spender, 250, expiration, 1) else {
+        unreachable!()
+    };
+    let err = setup.execute_err(&relayer, &ExecuteMsg::Permit {
+        owner: owner.to_string(),
+        spender: spender.to_string(),
+        amount: Uint128::from(250u128),
+        expiration,
+        nonce: 1,
+        signature,
+        pubkey,
+    });
+    assert_eq!(err.to_string(), ContractError::PubkeyMismatch {}.to_string());
+
+    let (_, permit) = signed_permit(&setup, &key, &spender, 250, expiration, 1);
+    setup.app.update_block(|block| block.time = block.time.plus_seconds(600));
+    let err = setup.execute_err(&relayer, &permit);
+    assert_eq!(err.to_string(), ContractError::PermitExpired {}.to_string());
+}
+
+#[test]
+fn test_compliance_freeze() {
+    // permissionless tokens can't freeze anyone
+    let mut setup = TestSetup::new();
+    let owner = setup.owner.clone();
+    let user1 = setup.addr("user1");
+    let status: ComplianceStatusResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.token_addr, &QueryMsg::ComplianceStatus {})
+        .unwrap();
+    assert!(!status.compliance);
+    let err = setup.execute_err(&owner, &ExecuteMsg::Freeze { address: user1.to_string() });
+    assert_eq!(err.to_string(), ContractError::ComplianceDisabled {}.to_string());
+    let err = setup.execute_err(&owner, &ExecuteMsg::ForceBurn {
+        address: user1.to_string(),
+        amount: Uint128::zero(),
+    });
+    assert_eq!(err.to_string(), ContractError::ComplianceDisabled {}.to_string());
+
+    let mut setup = TestSetup::instantiate(&[("user1", 500), ("user2", 500)], None, true).unwrap();
+    let owner = setup.owner.clone();
+    let user1 = setup.addr("user1");
+    let user2 = setup.addr("user2");
+    let spender = setup.addr("spender");
+
+    let status: ComplianceStatusResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.token_addr, &QueryMsg::ComplianceStatus {})
+        .unwrap();
+    assert!(status.compliance);
+    // TokenInfo stays exactly what cw20-base tooling expects
+    let info: cw20::TokenInfoResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.token_addr, &QueryMsg::TokenInfo {})
+        .unwrap();
+    assert_eq!(info.total_supply, Uint128::from(1000u128));
+
+    let err = setup.execute_err(&user2, &ExecuteMsg::Freeze { address: user1.to_string() });
+    assert_eq!(err.to_string(), ContractError::Ownership(cw_ownable::OwnershipError::NotOwner).to_string());
+
+    setup.execute(&user1, &ExecuteMsg::Approve {
+        spender: spender.to_string(),
+        amount: Uint128::from(100u128),
+    });
+    setup.execute(&owner, &ExecuteMsg::Freeze { address: user1.to_string() });
+
+    let frozen = ContractError::AccountFrozen { address: user1.to_string() }.to_string();
+    let err = setup.execute_err(&user1, &ExecuteMsg::Transfer {
+        recipient: user2.to_string(),
+        amount: Uint128::from(10u128),
+    });
+    assert_eq!(err.to_string(), frozen);
+
+    let err = setup.execute_err(&user2, &ExecuteMsg::Transfer {
+        recipient: user1.to_string(),
+        amount: Uint128::from(10u128),
+    });
+    assert_eq!(err.to_string(), frozen);
+
+    let err = setup.execute_err(&user2, &ExecuteMsg::BatchTransfer {
+        transfers: vec![Cw20Coin {
+            address: user1.to_string(),
+            amount: Uint128::from(10u128),
+        }],
+    });
+    assert_eq!(err.to_string(), frozen);
+
+    let err = setup.execute_err(&spender, &ExecuteMsg::TransferFrom {
+        owner: user1.to_string(),
+        recipient: spender.to_string(),
+        amount: Uint128::from(10u128),
+    });
+    assert_eq!(err.to_string(), frozen);
+
+    let err = setup.execute_err(&user1, &ExecuteMsg::Burn { amount: Uint128::from(10u128) });
+    assert_eq!(err.to_string(), frozen);
+
+    let res: FrozenAccountsResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.token_addr, &QueryMsg::FrozenAccounts {
+            start_after: None,
+            limit: None,
+        })
+        .unwrap();
+    assert_eq!(res.accounts, vec![user1.to_string()]);
+
+    // the owner can still take funds out of a frozen account
+    let force_burn = ExecuteMsg::ForceBurn {
+        address: user1.to_string(),
+        amount: Uint128::from(100u128),
+    };
+    let err = setup.execute_err(&user2, &force_burn);
+    assert_eq!(err.to_string(), ContractError::Ownership(cw_ownable::OwnershipError::NotOwner).to_string());
+    setup.execute(&owner, &force_burn);
+    assert_eq!(setup.balance(&user1), Uint128::from(400u128));
+    let supply: TokenInfoResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.token_addr, &QueryMsg::TokenInfo {})
+        .unwrap();
+    assert_eq!(supply.total_supply, Uint128::from(900u128));
+
+    setup.execute(&owner, &ExecuteMsg::Unfreeze { address: user1.to_string() });
+    setup.execute(&spender, &ExecuteMsg::TransferFrom {
+        owner: user1.to_string(),
+        recipient: spender.to_string(),
+        amount: Uint128::from(10u128),
+    });
+    assert_eq!(setup.balance(&spender), Uint128::from(10u128));
+}
+
+#[test]
+fn test_pause() {
+    let mut setup = TestSetup::new();
+    let owner = setup.owner.clone();
+    let user1 = setup.addr("user1");
+    let user2 = setup.addr("user2");
+    setup.mint(&user1, 100);
+    setup.execute(&user1, &ExecuteMsg::Approve {
+        spender: user2.to_string(),
+        amount: Uint128::from(50u128),
+    });
+
+    let err = setup.execute_err(&user1, &ExecuteMsg::Pause {});
+    assert_eq!(err.to_string(), ContractError::Ownership(cw_ownable::OwnershipError::NotOwner).to_string());
+
+    let res = setup.app
+        .execute_contract(owner.clone(), setup.token_addr.clone(), &ExecuteMsg::Pause {}, &[])
+        .unwrap();
+    assert!(res.events.iter().any(|e| e.attributes.iter().any(|a| a.key == "action" && a.value == "pause")));
+
+    let status: PauseStatusResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.token_addr, &QueryMsg::PauseStatus {})
+        .unwrap();
+    assert!(status.paused);
+
+    let paused = ContractError::Paused {}.to_string();
+    let blocked = [
+        (user1.clone(), ExecuteMsg::Transfer { recipient: user2.to_string(), amount: Uint128::one() }),
+        (user2.clone(), ExecuteMsg::TransferFrom {
+            owner: user1.to_string(),
+            recipient: user2.to_string(),
+            amount: Uint128::one(),
+        }),
+        (user1.clone(), ExecuteMsg::Burn { amount: Uint128::one() }),
+        (owner.clone(), ExecuteMsg::Mint { recipient: user1.to_string(), amount: Uint128::one() }),
+    ];
+    for (sender, msg) in &blocked {
+        assert_eq!(setup.execute_err(sender, msg).to_string(), paused);
+    }
+
+    // queries keep working while paused
+    assert_eq!(setup.balance(&user1), Uint128::from(100u128));
+
+    setup.execute(&owner, &ExecuteMsg::Unpause {});
+    for (sender, msg) in &blocked {
+        setup.execute(sender, msg);
+    }
+    assert_eq!(setup.balance(&user1), Uint128::from(98u128));
+    assert_eq!(setup.balance(&user2), Uint128::from(2u128));
+}
+
+#[test]
+fn test_transfer_fee() {
+    let mut setup = TestSetup::new();
+    let owner = setup.owner.clone();
+    let user1 = setup.addr("user1");
+    let user2 = setup.addr("user2");
+    let treasury = setup.addr("treasury");
+    let staking = setup.addr("staking");
+    setup.mint(&user1, 10_000);
+
+    let err = setup.execute_err(&owner, &ExecuteMsg::UpdateTransferFee {
+        transfer_fee_bps: Some(1_001),
+        fee_recipient: Some(treasury.to_string()),
+    });
+    assert_eq!(err.to_string(), ContractError::InvalidTransferFee { max: 1_000 }.to_string());
+
+    let err = setup.execute_err(&owner, &ExecuteMsg::UpdateTransferFee {
+        transfer_fee_bps: Some(100),
+        fee_recipient: None,
+    });
+    assert_eq!(err.to_string(), ContractError::InvalidTransferFee { max: 1_000 }.to_string());
+
+    // 1%
+    setup.execute(&owner, &ExecuteMsg::UpdateTransferFee {
+        transfer_fee_bps: Some(100),
+        fee_recipient: Some(treasury.to_string()),
+    });
+
+    let quote = |setup: &TestSetup, sender: &Addr, recipient: &Addr, amount: u128| {
+        let res: QuoteTransferResponse = setup.app
+            .wrap()
+            .query_wasm_smart(&setup.token_addr, &QueryMsg::QuoteTransfer {
+                sender: sender.to_string(),
+                recipient: recipient.to_string(),
+                amount: Uint128::from(amount),
+            })
+            .unwrap();
+        (res.fee.u128(), res.net_amount.u128())
+    };
+    assert_eq!(quote(&setup, &user1, &user2, 1_000), (10, 990));
+
+    setup.execute(&user1, &ExecuteMsg::Transfer {
+        recipient: user2.to_string(),
+        amount: Uint128::from(1_000u128),
+    });
+    assert_eq!(setup.balance(&user1), Uint128::from(9_000u128));
+    assert_eq!(setup.balance(&user2), Uint128::from(990u128));
+    assert_eq!(setup.balance(&treasury), Uint128::from(10u128));
+
+    // TransferFrom charges the owner's transfer the same way
+    setup.execute(&user1, &ExecuteMsg::Approve {
+        spender: user2.to_string(),
+        amount: Uint128::from(500u128),
+    });
+    setup.execute(&user2, &ExecuteMsg::TransferFrom {
+        owner: user1.to_string(),
+        recipient: user2.to_string(),
+        amount: Uint128::from(500u128),
+    });
+    assert_eq!(setup.balance(&user2), Uint128::from(1_485u128));
+    assert_eq!(setup.balance(&treasury), Uint128::from(15u128));
+
+    let err = setup.execute_err(&user1, &ExecuteMsg::UpdateFeeExemptions {
+        add: vec![staking.to_string()],
+        remove: vec![],
+    });
+    assert_eq!(err.to_string(), ContractError::Ownership(cw_ownable::OwnershipError::NotOwner).to_string());
+
+    setup.execute(&owner, &ExecuteMsg::UpdateFeeExemptions {
+        add: vec![staking.to_string()],
+        remove: vec![],
+    });
+    let res: FeeExemptResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.token_addr, &QueryMsg::FeeExempt { address: staking.to_string() })
+        .unwrap();
+    assert!(res.exempt);
+    assert_eq!(quote(&setup, &user1, &staking, 1_000), (0, 1_000));
+
+    setup.execute(&user1, &ExecuteMsg::Transfer {
+        recipient: staking.to_string(),
+        amount: Uint128::from(1_000u128),
+    });
+    assert_eq!(setup.balance(&staking), Uint128::from(1_000u128));
+    assert_eq!(setup.balance(&treasury), Uint128::from(15u128));
+
+    // turning the fee off
+    setup.execute(&owner, &ExecuteMsg::UpdateTransferFee {
+        transfer_fee_bps: None,
+        fee_recipient: None,
+    });
+    assert_eq!(quote(&setup, &user1, &user2, 1_000), (0, 1_000));
+}
+
+#[test]
+fn test_burn_from_and_event_attributes() {
+    let mut setup = TestSetup::new();
+    let owner = setup.owner.clone();
+    let user1 = setup.addr("user1");
+    let spender = setup.addr("spender");
+
+    let wasm_attrs = |res: &cw_multi_test::AppResponse| -> Vec<(String, String)> {
+        res.events
+            .iter()
+            .find(|e| e.ty == "wasm")
+            .unwrap()
+            .attributes
+            .iter()
+            .filter(|a| a.key != "_contract_address")
+            .map(|a| (a.key.clone(), a.value.clone()))
+            .collect()
+    };
+    let attrs = |pairs: &[(&str, &str)]| -> Vec<(String, String)> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    };
+
+    let res = setup.app
+        .execute_contract(owner.clone(), setup.token_addr.clone(), &ExecuteMsg::Mint {
+            recipient: user1.to_string(),
+            amount: Uint128::from(1_000u128),
+        }, &[])
+        .unwrap();
+    assert_eq!(wasm_attrs(&res), attrs(&[
+        ("action", "mint"),
+        ("to", user1.as_str()),
+        ("amount", "1000"),
+    ]));
+
+    setup.execute(&user1, &ExecuteMsg::Approve {
+        spender: spender.to_string(),
+        amount: Uint128::from(300u128),
+    });
+
+    let res = setup.app
+        .execute_contract(spender.clone(), setup.token_addr.clone(), &ExecuteMsg::TransferFrom {
+            owner: user1.to_string(),
+            recipient: spender.to_string(),
+            amount: Uint128::from(100u128),
+        }, &[])
+        .unwrap();
+    assert_eq!(wasm_attrs(&res)[..5], attrs(&[
+        ("action", "transfer_from"),
+        ("from", user1.as_str()),
+        ("to", spender.as_str()),
+        ("by", spender.as_str()),
+        ("amount", "100"),
+    ]));
+
+    let err = setup.execute_err(&spender, &ExecuteMsg::BurnFrom {
+        owner: user1.to_string(),
+        amount: Uint128::from(201u128),
+    });
+    assert_eq!(err.to_string(), ContractError::InsufficientAllowance {}.to_string());
+
+    let res = setup.app
+        .execute_contract(spender.clone(), setup.token_addr.clone(), &ExecuteMsg::BurnFrom {
+            owner: user1.to_string(),
+            amount: Uint128::from(200u128),
+        }, &[])
+        .unwrap();
+    assert_eq!(wasm_attrs(&res), attrs(&[
+        ("action", "burn_from"),
+        ("from", user1.as_str()),
+        ("by", spender.as_str()),
+        ("amount", "200"),
+    ]));
+
+    assert_eq!(setup.balance(&user1), Uint128::from(700u128));
+    let info: TokenInfoResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.token_addr, &QueryMsg::TokenInfo {})
+        .unwrap();
+    assert_eq!(info.total_supply, Uint128::from(800u128));
+}
+#[test]
+fn test_send_and_send_from() {
+    let mut setup = TestSetup::instantiate(&[("user1", 10_000)], None, true).unwrap();
+    let owner = setup.owner.clone();
+    let user1 = setup.addr("user1");
+    let spender = setup.addr("spender");
+    let treasury = setup.addr("treasury");
+
+    let receiver_code_id = setup.app.store_code(receiver_contract());
+    let receiver = setup.app
+        .instantiate_contract(receiver_code_id, owner.clone(), &Empty {}, &[], "receiver", None)
+        .unwrap();
+    let received = |setup: &TestSetup| -> Vec<Cw20ReceiveMsg> {
+        setup.app.wrap().query_wasm_smart(&receiver, &Empty {}).unwrap()
+    };
+
+    // 1%
+    setup.execute(&owner, &ExecuteMsg::UpdateTransferFee {
+        transfer_fee_bps: Some(100),
+        fee_recipient: Some(treasury.to_string()),
+    });
+
+    let send = ExecuteMsg::Send {
+        contract: receiver.to_string(),
+        amount: Uint128::from(1_000u128),
+        msg: Binary::from(b"stake"),
+    };
+    setup.execute(&user1, &send);
+    assert_eq!(setup.balance(&user1), Uint128::from(9_000u128));
+    assert_eq!(setup.balance(&receiver), Uint128::from(990u128));
+    assert_eq!(setup.balance(&treasury), Uint128::from(10u128));
+
+    // the hook is told what actually arrived
+    assert_eq!(received(&setup), vec![Cw20ReceiveMsg {
+        sender: user1.to_string(),
+        amount: Uint128::from(990u128),
+        msg: Binary::from(b"stake"),
+    }]);
+
+    setup.execute(&user1, &ExecuteMsg::Approve {
+        spender: spender.to_string(),
+        amount: Uint128::from(500u128),
+    });
+    let send_from = |amount: u128| ExecuteMsg::SendFrom {
+        owner: user1.to_string(),
+        contract: receiver.to_string(),
+        amount: Uint128::from(amount),
+        msg: Binary::from(b"stake"),
+    };
+    setup.execute(&spender, &send_from(400));
+    assert_eq!(setup.balance(&user1), Uint128::from(8_600u128));
+    assert_eq!(setup.balance(&receiver), Uint128::from(1_386u128));
+    assert_eq!(setup.allowance(&user1, &spender).allowance, Uint128::from(100u128));
+    assert_eq!(received(&setup)[1], Cw20ReceiveMsg {
+        sender: spender.to_string(),
+        amount: Uint128::from(396u128),
+        msg: Binary::from(b"stake"),
+    });
+
+    let err = setup.execute_err(&spender, &send_from(101));
+    assert_eq!(err.to_string(), ContractError::InsufficientAllowance {}.to_string());
+
+    setup.execute(&owner, &ExecuteMsg::Pause {});
+    let paused = ContractError::Paused {}.to_string();
+    assert_eq!(setup.execute_err(&user1, &send).to_string(), paused);
+    assert_eq!(setup.execute_err(&spender, &send_from(10)).to_string(), paused);
+    setup.execute(&owner, &ExecuteMsg::Unpause {});
+
+    setup.execute(&owner, &ExecuteMsg::Freeze { address: user1.to_string() });
+    let frozen = ContractError::AccountFrozen { address: user1.to_string() }.to_string();
+    assert_eq!(setup.execute_err(&user1, &send).to_string(), frozen);
+    assert_eq!(setup.execute_err(&spender, &send_from(10)).to_string(), frozen);
+    setup.execute(&owner, &ExecuteMsg::Unfreeze { address: user1.to_string() });
+
+    setup.execute(&owner, &ExecuteMsg::Freeze { address: receiver.to_string() });
+    let frozen = ContractError::AccountFrozen { address: receiver.to_string() }.to_string();
+    assert_eq!(setup.execute_err(&user1, &send).to_string(), frozen);
+    assert_eq!(setup.execute_err(&spender, &send_from(10)).to_string(), frozen);
+
+    assert_eq!(received(&setup).len(), 2);
+    assert_eq!(setup.balance(&user1), Uint128::from(8_600u128));
+}
+
+#[test]
+fn test_cw20_base_instantiate() {
+    // a cw20-base message is told apart from our own by its fields
+    let raw = br#"{"name":"Test Token","symbol":"TST","decimals":6,"initial_balances":[],"mint":null,"marketing":null}"#;
+    assert!(matches!(from_json::<InstantiateMode>(raw).unwrap(), InstantiateMode::Cw20Base(_)));
+
+    let mut app = App::default();
+    let creator = app.api().addr_make("creator");
+    let user1 = app.api().addr_make("user1");
+    let code_id = app.store_code(cw20_contract());
+    let token_addr = app
+        .instantiate_contract(
+            code_id,
+            creator.clone(),
+            &Cw20BaseInstantiateMsg {
+                name: "Test Token".to_string(),
+                symbol: "TST".to_string(),
+                decimals: 6,
+                initial_balances: vec![Cw20Coin {
+                    address: user1.to_string(),
+                    amount: Uint128::from(1000u128),
+                }],
+                mint: Some(cw20::MinterResponse {
+                    minter: creator.to_string(),
+                    cap: Some(Uint128::from(2000u128)),
+                }),
+                marketing: None,
+            },
+            &[],
+            "Test Token",
+            None,
+        )
+        .unwrap();
+
+    let minter: MinterResponse = app
+        .wrap()
+        .query_wasm_smart(&token_addr, &QueryMsg::Minter {})
+        .unwrap();
+    assert_eq!(minter.minter, Some(creator.to_string()));
+    assert_eq!(minter.cap, Some(Uint128::from(2000u128)));
+
+    // the creator owns the token, nothing cw20-base lacks is switched on
+    let ownership: cw_ownable::Ownership<Addr> = app
+        .wrap()
+        .query_wasm_smart(&token_addr, &QueryMsg::Ownership {})
+        .unwrap();
+    assert_eq!(ownership.owner, Some(creator.clone()));
+    let compliance: ComplianceStatusResponse = app
+        .wrap()
+        .query_wasm_smart(&token_addr, &QueryMsg::ComplianceStatus {})
+        .unwrap();
+    assert!(!compliance.compliance);
+    let quote: QuoteTransferResponse = app
+        .wrap()
+        .query_wasm_smart(&token_addr, &QueryMsg::QuoteTransfer {
+            sender: user1.to_string(),
+            recipient: creator.to_string(),
+            amount: Uint128::from(100u128),
+        })
+        .unwrap();
+    assert_eq!(quote.fee, Uint128::zero());
+
+    let info: cw20::TokenInfoResponse = app
+        .wrap()
+        .query_wasm_smart(&token_addr, &cw20::Cw20QueryMsg::TokenInfo {})
+        .unwrap();
+    assert_eq!(info.total_supply, Uint128::from(1000u128));
+}
+
+#[test]
+fn test_transfer_hooks() {
+    let mut setup = TestSetup::instantiate(&[("user1", 10_000)], None, false).unwrap();
+    let owner = setup.owner.clone();
+    let user1 = setup.addr("user1");
+    let user2 = setup.addr("user2");
+    let treasury = setup.addr("treasury");
+
+    let hook_code_id = setup.app.store_code(hook_contract(false));
+    let hook = setup.app
+        .instantiate_contract(hook_code_id, owner.clone(), &Empty {}, &[], "hook", None)
+        .unwrap();
+    let hooked = |setup: &TestSetup| -> Vec<TransferHookMsg> {
+        setup.app.wrap().query_wasm_smart(&hook, &Empty {}).unwrap()
+    };
+
+    let add_hook = ExecuteMsg::AddHook { addr: hook.to_string() };
+    let err = setup.execute_err(&user1, &add_hook);
+    assert_eq!(err.to_string(), ContractError::Ownership(cw_ownable::OwnershipError::NotOwner).to_string());
+    setup.execute(&owner, &add_hook);
+    let err = setup.execute_err(&owner, &add_hook);
+    assert_eq!(err.to_string(), ContractError::HookAlreadyRegistered {}.to_string());
+
+    let hooks: HooksResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.token_addr, &QueryMsg::Hooks {})
+        .unwrap();
+    assert_eq!(hooks.hooks, vec![hook.to_string()]);
+
+    // 1%, hooks are told what the recipient got
+    setup.execute(&owner, &ExecuteMsg::UpdateTransferFee {
+        transfer_fee_bps: Some(100),
+        fee_recipient: Some(treasury.to_string()),
+    });
+    setup.execute(&user1, &ExecuteMsg::Transfer {
+        recipient: user2.to_string(),
+        amount: Uint128::from(1_000u128),
+    });
+    setup.execute(&user1, &ExecuteMsg::IncreaseAllowance {
+        spender: user2.to_string(),
+        amount: Uint128::from(500u128),
+        expires: None,
+    });
+    setup.execute(&user2, &ExecuteMsg::TransferFrom {
+        owner: user1.to_string(),
+        recipient: user2.to_string(),
+        amount: Uint128::from(500u128),
+    });
+    setup.execute(&user1, &ExecuteMsg::BatchTransfer {
+        transfers: vec![
+            Cw20Coin { address: user2.to_string(), amount: Uint128::from(100u128) },
+            Cw20Coin { address: owner.to_string(), amount: Uint128::from(200u128) },
+        ],
+    });
+    // burns and mints don't move tokens between accounts
+    setup.execute(&user2, &ExecuteMsg::Burn { amount: Uint128::from(10u128) });
+    setup.mint(&user2, 10);
+
+    let transfer = |from: &Addr, to: &Addr, amount: u128| TransferHookMsg {
+        from: from.to_string(),
+        to: to.to_string(),
+        amount: Uint128::from(amount),
+    };
+    assert_eq!(hooked(&setup), vec![
+        transfer(&user1, &user2, 990),
+        transfer(&user1, &user2, 495),
+        transfer(&user1, &user2, 99),
+        transfer(&user1, &owner, 198),
+    ]);
+
+    setup.execute(&owner, &ExecuteMsg::RemoveHook { addr: hook.to_string() });
+    let err = setup.execute_err(&owner, &ExecuteMsg::RemoveHook { addr: hook.to_string() });
+    assert_eq!(err.to_string(), ContractError::HookNotRegistered {}.to_string());
+    setup.execute(&user1, &ExecuteMsg::Transfer {
+        recipient: user2.to_string(),
+        amount: Uint128::one(),
+    });
+    assert_eq!(hooked(&setup).len(), 4);
+
+    // every hook is messaged on every transfer, so their number is bounded
+    for i in 0..10 {
+        setup.execute(&owner, &ExecuteMsg::AddHook { addr: setup.addr(&format!("hook{i}")).to_string() });
+    }
+    let err = setup.execute_err(&owner, &add_hook);
+    assert_eq!(err.to_string(), ContractError::TooManyHooks { max: 10 }.to_string());
+}
+
+#[test]
+fn test_rejecting_hook_reverts_transfer() {
+    let mut setup = TestSetup::instantiate(&[("user1", 1000)], None, false).unwrap();
+    let owner = setup.owner.clone();
+    let user1 = setup.addr("user1");
+    let user2 = setup.addr("user2");
+
+    // nothing could move tokens back out of the token contract
+    let token_addr = setup.token_addr.clone();
+    let err = setup.execute_err(&user1, &ExecuteMsg::Transfer {
+        recipient: token_addr.to_string(),
+        amount: Uint128::one(),
+    });
+    assert_eq!(err.to_string(), ContractError::TransferToSelf {}.to_string());
+
+    let hook_code_id = setup.app.store_code(hook_contract(true));
+    let hook = setup.app
+        .instantiate_contract(hook_code_id, owner.clone(), &Empty {}, &[], "hook", None)
+        .unwrap();
+    setup.execute(&owner, &ExecuteMsg::AddHook { addr: hook.to_string() });
+
+    let err = setup.app
+        .execute_contract(user1.clone(), setup.token_addr.clone(), &ExecuteMsg::Transfer {
+            recipient: user2.to_string(),
+            amount: Uint128::from(100u128),
+        }, &[])
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Generic error: transfer rejected by hook");
+    assert_eq!(setup.balance(&user1), Uint128::from(1000u128));
+    assert_eq!(setup.balance(&user2), Uint128::zero());
+
+    // once ownership is renounced the hook list is fixed
+    setup.execute(&owner, &ExecuteMsg::UpdateOwnership(cw_ownable::Action::RenounceOwnership));
+    let err = setup.execute_err(&owner, &ExecuteMsg::RemoveHook { addr: hook.to_string() });
+    assert_eq!(err.to_string(), ContractError::Ownership(cw_ownable::OwnershipError::NoOwner).to_string());
+}
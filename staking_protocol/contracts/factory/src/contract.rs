@@ -0,0 +1,370 @@
+use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, entry_point, to_json_binary, StdResult, WasmMsg, Reply, SubMsg, Addr, Empty, Order, StdError};
+use cw_ownable::initialize_owner;
+use cw_storage_plus::Bound;
+use shared::parse_reply_instantiate_data;
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, CreatorResponse, ExecuteMsg, InstantiateMsg, PoolResponse, PoolsResponse,
+    QueryMsg, StakeTokenMsg,
+};
+use crate::state::{
+    Config, PendingPool, PoolInfo, CONFIG, CREATORS, PENDING_POOL, POOLS, POOLS_BY_TOKEN,
+};
+
+const INSTANTIATE_TOKEN_REPLY_ID: u64 = 1;
+const INSTANTIATE_STAKING_REPLY_ID: u64 = 2;
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+#[entry_point]
+pub fn instantiate (
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    initialize_owner(deps.storage, deps.api, Some(&msg.owner))?;
+
+    CONFIG.save(deps.storage, &Config {
+        staking_code_id: msg.staking_code_id,
+        token_code_id: msg.token_code_id,
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate"))
+}
+
+#[entry_point]
+pub fn execute (
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::UpdateOwnership(action) => {
+            cw_ownable::update_ownership(deps, &env.block, &info.sender, action)?;
+            Ok(Response::new().add_attribute("action", "update_ownership"))
+        }
+        ExecuteMsg::CreatePool { stake_token, reward_rate, lockup_period, pool_owner } => {
+            execute_create_pool(deps, env, info, stake_token, reward_rate, lockup_period, pool_owner)
+        }
+        ExecuteMsg::UpdateConfig { staking_code_id, token_code_id } => {
+            execute_update_config(deps, info, staking_code_id, token_code_id)
+        }
+        ExecuteMsg::UpdateCreators { add, remove } => {
+            execute_update_creators(deps, info, add, remove)
+        }
+    }
+}
+
+#[entry_point]
+pub fn reply (
+    deps: DepsMut,
+    _env: Env,
+    msg: Reply,
+) -> Result<Response, ContractError> {
+    match msg.id {
+        INSTANTIATE_TOKEN_REPLY_ID => {
+            let res = parse_reply_instantiate_data(msg)?;
+            let token = deps.api.addr_validate(&res.contract_address)?;
+
+            let mut pending = PENDING_POOL.load(deps.storage)?;
+            pending.stake_token = Some(token.clone());
+            PENDING_POOL.save(deps.storage, &pending)?;
+
+            let config = CONFIG.load(deps.storage)?;
+            Ok(Response::new()
+                .add_submessage(instantiate_staking_msg(&config, &pending, &token)?)
+                .add_attribute("action", "create_stake_token")
+                .add_attribute("stake_token", token))
+        }
+        INSTANTIATE_STAKING_REPLY_ID => {
+            let res = parse_reply_instantiate_data(msg)?;
+            let staking_address = deps.api.addr_validate(&res.contract_address)?;
+
+            let pending = PENDING_POOL.load(deps.storage)?;
+            PENDING_POOL.remove(deps.storage);
+            let stake_token = pending.stake_token
+                .ok_or_else(|| StdError::generic_err("Pending pool has no stake token"))?;
+
+            POOLS.save(deps.storage, &staking_address, &PoolInfo {
+                staking_address: staking_address.clone(),
+                stake_token: stake_token.clone(),
+                owner: pending.owner,
+                minter_wired: pending.new_token,
+            })?;
+            POOLS_BY_TOKEN.save(deps.storage, (&stake_token, &staking_address), &Empty {})?;
+
+            let mut response = Response::new();
+            if pending.new_token {
+                // rewards are minted by the pool, so it takes over the minter
+                response = response.add_message(WasmMsg::Execute {
+                    contract_addr: stake_token.to_string(),
+                    msg: to_json_binary(&cw20_token::msg::ExecuteMsg::UpdateMinter {
+                        new_minter: Some(staking_address.to_string()),
+                    })?,
+                    funds: vec![],
+                });
+            }
+
+            Ok(response
+                .add_attribute("action", "register_pool")
+                .add_attribute("staking_address", staking_address)
+                .add_attribute("stake_token", stake_token)
+                .add_attribute("minter_wired", pending.new_token.to_string()))
+        }
+        id => Err(ContractError::UnknownReplyId { id }),
+    }
+}
+
+#[entry_point]
+pub fn query (
+    deps: Deps,
+    _env: Env,
+    msg: QueryMsg,
+) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Ownership {} => {
+            to_json_binary(&cw_ownable::get_ownership(deps.storage)?)
+        }
+        QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
+        QueryMsg::Creator {address} => to_json_binary(&query_creator(deps, address)?),
+        QueryMsg::Pool {address} => to_json_binary(&query_pool(deps, address)?),
+        QueryMsg::Pools { start_after, limit } => {
+            to_json_binary(&query_pools(deps, start_after, limit)?)
+        }
+        QueryMsg::PoolsByToken { token, start_after, limit } => {
+            to_json_binary(&query_pools_by_token(deps, token, start_after, limit)?)
+        }
+    }
+}
+
+pub fn execute_create_pool(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    stake_token: StakeTokenMsg,
+    reward_rate: u64,
+    lockup_period: u64,
+    pool_owner: Option<String>,
+) -> Result<Response, ContractError> {
+    if !CREATORS.has(deps.storage, &info.sender) {
+        cw_ownable::assert_owner(deps.storage, &info.sender)
+            .map_err(|_| ContractError::Unauthorized {})?;
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let owner = match pool_owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender.clone(),
+    };
+
+    let mut pending = PendingPool {
+        stake_token: None,
+        new_token: false,
+        reward_rate,
+        lockup_period,
+        owner,
+    };
+
+    let submsg = match stake_token {
+        StakeTokenMsg::Existing { address } => {
+            let token = deps.api.addr_validate(&address)?;
+            pending.stake_token = Some(token.clone());
+            instantiate_staking_msg(&config, &pending, &token)?
+        }
+        StakeTokenMsg::New { name, symbol, decimals, initial_balances } => {
+            pending.new_token = true;
+            SubMsg::reply_on_success(
+                WasmMsg::Instantiate {
+                    admin: None,
+                    code_id: config.token_code_id,
+                    msg: to_json_binary(&cw20_token::msg::InstantiateMsg {
+                        name: name.clone(),
+                        symbol,
+                        decimals,
+                        initial_balances,
+                        owner: Some(pending.owner.to_string()),
+                        minter: Some(env.contract.address.to_string()),
+                        cap: None,
+                        marketing: None,
+                        compliance: false,
+                        transfer_fee_bps: None,
+                        fee_recipient: None,
+                    })?,
+                    funds: vec![],
+                    label: name,
+                },
+                INSTANTIATE_TOKEN_REPLY_ID,
+            )
+        }
+    };
+
+    PENDING_POOL.save(deps.storage, &pending)?;
+
+    Ok(Response::new()
+        .add_submessage(submsg)
+        .add_attribute("action", "create_pool")
+        .add_attribute("pool_owner", pending.owner))
+}
+
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    staking_code_id: Option<u64>,
+    token_code_id: Option<u64>,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let config = CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        if let Some(code_id) = staking_code_id {
+            config.staking_code_id = code_id;
+        }
+        if let Some(code_id) = token_code_id {
+            config.token_code_id = code_id;
+        }
+        Ok(config)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_config")
+        .add_attribute("staking_code_id", config.staking_code_id.to_string())
+        .add_attribute("token_code_id", config.token_code_id.to_string()))
+}
+
+pub fn execute_update_creators(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    for address in &add {
+        let addr = deps.api.addr_validate(address)?;
+        CREATORS.save(deps.storage, &addr, &Empty {})?;
+    }
+    for address in &remove {
+        let addr = deps.api.addr_validate(address)?;
+        CREATORS.remove(deps.storage, &addr);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "update_creators")
+        .add_attribute("added", add.len().to_string())
+        .add_attribute("removed", remove.len().to_string()))
+}
+
+fn instantiate_staking_msg(
+    config: &Config,
+    pending: &PendingPool,
+    token: &Addr,
+) -> StdResult<SubMsg> {
+    let msg = WasmMsg::Instantiate {
+        admin: Some(pending.owner.to_string()),
+        code_id: config.staking_code_id,
+        msg: to_json_binary(&staking::msg::InstantiateMsg {
+            owner: pending.owner.to_string(),
+            token_address: token.to_string(),
+            reward_rate: pending.reward_rate,
+            lockup_period: pending.lockup_period,
+            max_total_stake: None,
+            max_stake_per_address: None,
+            whitelist: None,
+            receipt_token: None,
+            reward_vesting: None,
+            slasher: None,
+            slash_recipient: None,
+        })?,
+        funds: vec![],
+        label: format!("staking pool {}", token),
+    };
+
+    Ok(SubMsg::reply_on_success(msg, INSTANTIATE_STAKING_REPLY_ID))
+}
+
+fn query_config(
+    deps: Deps,
+) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        staking_code_id: config.staking_code_id,
+        token_code_id: config.token_code_id,
+    })
+}
+
+fn query_creator(
+    deps: Deps,
+    address: String,
+) -> StdResult<CreatorResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    Ok(CreatorResponse {
+        allowed: CREATORS.has(deps.storage, &addr),
+    })
+}
+
+fn query_pool(
+    deps: Deps,
+    address: String,
+) -> StdResult<PoolResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let pool = POOLS.load(deps.storage, &addr)?;
+    Ok(pool_response(pool))
+}
+
+fn query_pools(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<PoolsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|s| deps.api.addr_validate(&s))
+        .transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let pools = POOLS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, pool)| pool_response(pool)))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(PoolsResponse { pools })
+}
+
+fn query_pools_by_token(
+    deps: Deps,
+    token: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<PoolsResponse> {
+    let token = deps.api.addr_validate(&token)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|s| deps.api.addr_validate(&s))
+        .transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let pools = POOLS_BY_TOKEN
+        .prefix(&token)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let pool = POOLS.load(deps.storage, &item?)?;
+            Ok(pool_response(pool))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(PoolsResponse { pools })
+}
+
+fn pool_response(pool: PoolInfo) -> PoolResponse {
+    PoolResponse {
+        staking_address: pool.staking_address.to_string(),
+        stake_token: pool.stake_token.to_string(),
+        owner: pool.owner.to_string(),
+        minter_wired: pool.minter_wired,
+    }
+}
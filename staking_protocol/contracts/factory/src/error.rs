@@ -1,18 +1,22 @@
-use cosmwasm_std::StdError;
-use thiserror::Error;
-use cw_ownable::OwnershipError;
-
-#[derive(Error, Debug)]
-pub enum ContractError {
-    #[error("{0}")]
-    Std(#[from] StdError),
-
-    #[error("{0}")]
-    Ownership(#[from] OwnershipError),
-
-    #[error("Insufficient balance")]
-    InsufficientBalance {},
-    
-    #[error("Insufficient allowance")]
-    InsufficientAllowance {},
+use cosmwasm_std::StdError;
+use thiserror::Error;
+use cw_ownable::OwnershipError;
+use cw_utils::ParseReplyError;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Ownership(#[from] OwnershipError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("{0}")]
+    ParseReply(#[from] ParseReplyError),
+
+    #[error("Unknown reply id: {id}")]
+    UnknownReplyId { id: u64 },
 }
\ No newline at end of file
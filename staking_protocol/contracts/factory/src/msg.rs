@@ -0,0 +1,100 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cw_ownable::Action;
+use shared::Cw20Coin;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub owner: String,
+    pub staking_code_id: u64,
+    pub token_code_id: u64,
+}
+
+#[cw_serde]
+pub enum StakeTokenMsg {
+    /// Pool over an already deployed cw20, its minter has to be handed
+    /// to the pool separately for rewards to be minted
+    Existing { address: String },
+    /// Instantiates a fresh cw20-token owned by the pool owner, its
+    /// minter becomes the pool
+    New {
+        name: String,
+        symbol: String,
+        decimals: u8,
+        initial_balances: Vec<Cw20Coin>,
+    },
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Owner or allowed creators only
+    CreatePool {
+        stake_token: StakeTokenMsg,
+        reward_rate: u64,
+        lockup_period: u64,
+        /// Owner of the new pool, defaults to the sender
+        pool_owner: Option<String>,
+    },
+    UpdateConfig {
+        staking_code_id: Option<u64>,
+        token_code_id: Option<u64>,
+    },
+    /// Owner only, manages who besides the owner may create pools
+    UpdateCreators {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    UpdateOwnership(Action),
+}
+
+#[derive(QueryResponses)]
+#[cw_serde]
+pub enum QueryMsg {
+    #[returns(ConfigResponse)]
+    Config {},
+
+    #[returns(CreatorResponse)]
+    Creator {address: String},
+
+    #[returns(PoolResponse)]
+    Pool {address: String},
+
+    #[returns(PoolsResponse)]
+    Pools {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(PoolsResponse)]
+    PoolsByToken {
+        token: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(cw_ownable::Ownership<cosmwasm_std::Addr>)]
+    Ownership {},
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub staking_code_id: u64,
+    pub token_code_id: u64,
+}
+
+#[cw_serde]
+pub struct CreatorResponse {
+    pub allowed: bool,
+}
+
+#[cw_serde]
+pub struct PoolResponse {
+    pub staking_address: String,
+    pub stake_token: String,
+    pub owner: String,
+    pub minter_wired: bool,
+}
+
+#[cw_serde]
+pub struct PoolsResponse {
+    pub pools: Vec<PoolResponse>,
+}
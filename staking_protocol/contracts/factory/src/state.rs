@@ -0,0 +1,36 @@
+use cosmwasm_std::{Addr, Empty};
+use cw_storage_plus::{Item, Map};
+use cosmwasm_schema::cw_serde;
+
+#[cw_serde]
+pub struct Config {
+    pub staking_code_id: u64,
+    pub token_code_id: u64,
+}
+
+#[cw_serde]
+pub struct PoolInfo {
+    pub staking_address: Addr,
+    pub stake_token: Addr,
+    pub owner: Addr,
+    // true when the factory handed the token minter over to the pool
+    pub minter_wired: bool,
+}
+
+// pool being created, kept between the instantiate replies
+#[cw_serde]
+pub struct PendingPool {
+    pub stake_token: Option<Addr>,
+    // the factory instantiated the token and still holds its minter
+    pub new_token: bool,
+    pub reward_rate: u64,
+    pub lockup_period: u64,
+    pub owner: Addr,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const PENDING_POOL: Item<PendingPool> = Item::new("pending_pool");
+pub const POOLS: Map<&Addr, PoolInfo> = Map::new("pools");
+// may create pools next to the owner
+pub const CREATORS: Map<&Addr, Empty> = Map::new("creators");
+pub const POOLS_BY_TOKEN: Map<(&Addr, &Addr), Empty> = Map::new("pools_by_token");
\ No newline at end of file
@@ -0,0 +1,321 @@
+use cosmwasm_std::{Addr, Empty, Uint128};
+use cw_multi_test::{App, ContractWrapper, Executor};
+
+use shared::Cw20Coin;
+use factory::ContractError;
+use factory::msg::{
+    CreatorResponse, ExecuteMsg, InstantiateMsg, PoolResponse, PoolsResponse, QueryMsg,
+    StakeTokenMsg,
+};
+
+fn cw20_contract() -> Box<dyn cw_multi_test::Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw20_token::contract::execute,
+        cw20_token::contract::instantiate,
+        cw20_token::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn staking_contract() -> Box<dyn cw_multi_test::Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        staking::contract::execute,
+        staking::contract::instantiate,
+        staking::contract::query,
+    )
+    .with_reply(staking::contract::reply);
+    Box::new(contract)
+}
+
+fn factory_contract() -> Box<dyn cw_multi_test::Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        factory::contract::execute,
+        factory::contract::instantiate,
+        factory::contract::query,
+    )
+    .with_reply(factory::contract::reply);
+    Box::new(contract)
+}
+
+struct TestSetup {
+    app: App,
+    factory_addr: Addr,
+    cw20_code_id: u64,
+    owner: Addr,
+}
+
+impl TestSetup {
+    fn new() -> Self {
+        let mut app = App::default();
+        let owner = app.api().addr_make("owner");
+        let cw20_code_id = app.store_code(cw20_contract());
+        let staking_code_id = app.store_code(staking_contract());
+        let factory_code_id = app.store_code(factory_contract());
+
+        let factory_addr = app
+            .instantiate_contract(
+                factory_code_id,
+                owner.clone(),
+                &InstantiateMsg {
+                    owner: owner.to_string(),
+                    staking_code_id,
+                    token_code_id: cw20_code_id,
+                },
+                &[],
+                "Staking factory",
+                None,
+            )
+            .unwrap();
+
+        TestSetup {
+            app,
+            factory_addr,
+            cw20_code_id,
+            owner,
+        }
+    }
+
+    fn addr(&self, name: &str) -> Addr {
+        self.app.api().addr_make(name)
+    }
+
+    fn allow_creator(&mut self, creator: &Addr) {
+        self.app
+            .execute_contract(
+                self.owner.clone(),
+                self.factory_addr.clone(),
+                &ExecuteMsg::UpdateCreators {
+                    add: vec![creator.to_string()],
+                    remove: vec![],
+                },
+                &[],
+            )
+            .unwrap();
+    }
+
+    fn try_create_pool(&mut self, sender: &Addr, stake_token: StakeTokenMsg) -> Result<(), ContractError> {
+        self.app
+            .execute_contract(
+                sender.clone(),
+                self.factory_addr.clone(),
+                &ExecuteMsg::CreatePool {
+                    stake_token,
+                    reward_rate: 1000,
+                    lockup_period: 60,
+                    pool_owner: None,
+                },
+                &[],
+            )
+            .map(|_| ())
+            .map_err(|err| err.downcast().unwrap())
+    }
+
+    fn create_pool(&mut self, sender: &Addr, stake_token: StakeTokenMsg) {
+        self.try_create_pool(sender, stake_token).unwrap();
+    }
+
+    fn pools(&self) -> Vec<PoolResponse> {
+        let res: PoolsResponse = self.app
+            .wrap()
+            .query_wasm_smart(&self.factory_addr, &QueryMsg::Pools {
+                start_after: None,
+                limit: None,
+            })
+            .unwrap();
+        res.pools
+    }
+}
+
+#[test]
+fn test_create_pool_with_new_token() {
+    let mut setup = TestSetup::new();
+    let creator = setup.addr("creator");
+    setup.allow_creator(&creator);
+
+    setup.create_pool(&creator, StakeTokenMsg::New {
+        name: "Pool Token".to_string(),
+        symbol: "POOL".to_string(),
+        decimals: 6,
+        initial_balances: vec![],
+    });
+
+    let pools = setup.pools();
+    assert_eq!(pools.len(), 1);
+    let pool = &pools[0];
+    assert!(pool.minter_wired);
+    assert_eq!(pool.owner, creator.to_string());
+
+    let minter: cw20_token::msg::MinterResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&pool.stake_token, &cw20_token::msg::QueryMsg::Minter {})
+        .unwrap();
+    assert_eq!(minter.minter, Some(pool.staking_address.clone()));
+
+    let config: staking::msg::ConfigResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&pool.staking_address, &staking::msg::QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.token_address, pool.stake_token);
+    assert_eq!(config.apr, 1000);
+
+    let queried: PoolResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.factory_addr, &QueryMsg::Pool {
+            address: pool.staking_address.clone(),
+        })
+        .unwrap();
+    assert_eq!(&queried, pool);
+}
+
+#[test]
+fn test_pools_by_existing_token() {
+    let mut setup = TestSetup::new();
+    let creator = setup.addr("creator");
+    setup.allow_creator(&creator);
+
+    let token_addr = setup.app
+        .instantiate_contract(
+            setup.cw20_code_id,
+            setup.owner.clone(),
+            &cw20_token::msg::InstantiateMsg {
+                name: "Test Token".to_string(),
+                symbol: "TST".to_string(),
+                decimals: 18,
+                initial_balances: vec![],
+                owner: None,
+                minter: Some(setup.owner.to_string()),
+                cap: None,
+                marketing: None,
+                compliance: false,
+                transfer_fee_bps: None,
+                fee_recipient: None,
+            },
+            &[],
+            "Test Token",
+            None,
+        )
+        .unwrap();
+
+    setup.create_pool(&creator, StakeTokenMsg::Existing { address: token_addr.to_string() });
+    setup.create_pool(&creator, StakeTokenMsg::Existing { address: token_addr.to_string() });
+    setup.create_pool(&creator, StakeTokenMsg::New {
+        name: "Other Token".to_string(),
+        symbol: "OTHR".to_string(),
+        decimals: 6,
+        initial_balances: vec![],
+    });
+
+    assert_eq!(setup.pools().len(), 3);
+
+    let by_token: PoolsResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.factory_addr, &QueryMsg::PoolsByToken {
+            token: token_addr.to_string(),
+            start_after: None,
+            limit: None,
+        })
+        .unwrap();
+    assert_eq!(by_token.pools.len(), 2);
+    assert!(by_token.pools.iter().all(|p| p.stake_token == token_addr.as_str() && !p.minter_wired));
+
+    // existing tokens keep their minter until it is handed over manually
+    let minter: cw20_token::msg::MinterResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&token_addr, &cw20_token::msg::QueryMsg::Minter {})
+        .unwrap();
+    assert_eq!(minter.minter, Some(setup.owner.to_string()));
+
+    let total: staking::msg::TotalStakedResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&by_token.pools[0].staking_address, &staking::msg::QueryMsg::TotalStaked {})
+        .unwrap();
+    assert_eq!(total.total, Uint128::zero());
+}
+
+#[test]
+fn test_create_pool_requires_owner_or_creator() {
+    let mut setup = TestSetup::new();
+    let creator = setup.addr("creator");
+    let new_token = || StakeTokenMsg::New {
+        name: "Pool Token".to_string(),
+        symbol: "POOL".to_string(),
+        decimals: 6,
+        initial_balances: vec![],
+    };
+
+    let err = setup.try_create_pool(&creator, new_token()).unwrap_err();
+    assert_eq!(err.to_string(), ContractError::Unauthorized {}.to_string());
+
+    let owner = setup.owner.clone();
+    setup.create_pool(&owner, new_token());
+
+    setup.allow_creator(&creator);
+    let allowed: CreatorResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.factory_addr, &QueryMsg::Creator {
+            address: creator.to_string(),
+        })
+        .unwrap();
+    assert!(allowed.allowed);
+    setup.create_pool(&creator, new_token());
+
+    assert_eq!(setup.pools().len(), 2);
+}
+
+#[test]
+fn test_stake_into_new_token_pool() {
+    let mut setup = TestSetup::new();
+    let owner = setup.owner.clone();
+    let user = setup.addr("user");
+    let amount = Uint128::from(1_000_000u128);
+
+    setup.create_pool(&owner, StakeTokenMsg::New {
+        name: "Pool Token".to_string(),
+        symbol: "POOL".to_string(),
+        decimals: 6,
+        initial_balances: vec![Cw20Coin { address: user.to_string(), amount }],
+    });
+    let pool = setup.pools().remove(0);
+    let token = Addr::unchecked(&pool.stake_token);
+    let staking = Addr::unchecked(&pool.staking_address);
+
+    setup.app
+        .execute_contract(
+            user.clone(),
+            token.clone(),
+            &cw20_token::msg::ExecuteMsg::Approve { spender: staking.to_string(), amount },
+            &[],
+        )
+        .unwrap();
+    setup.app
+        .execute_contract(
+            user.clone(),
+            staking.clone(),
+            &staking::msg::ExecuteMsg::Stake { amount },
+            &[],
+        )
+        .unwrap();
+
+    let total: staking::msg::TotalStakedResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&staking, &staking::msg::QueryMsg::TotalStaked {})
+        .unwrap();
+    assert_eq!(total.total, amount);
+
+    // the pool holds the minter, so rewards can be paid out
+    setup.app.update_block(|block| block.time = block.time.plus_seconds(60 * 60 * 24 * 365));
+    setup.app
+        .execute_contract(
+            user.clone(),
+            staking.clone(),
+            &staking::msg::ExecuteMsg::ClaimRewards {},
+            &[],
+        )
+        .unwrap();
+
+    let balance: cw20_token::msg::BalanceResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&token, &cw20_token::msg::QueryMsg::Balance { address: user.to_string() })
+        .unwrap();
+    assert_eq!(balance.balance, amount.multiply_ratio(1000u128, 10_000u128));
+}
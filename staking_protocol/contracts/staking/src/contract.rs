@@ -1,326 +1,1225 @@
-use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, entry_point, to_json_binary, Uint128, StdResult, WasmMsg, CosmosMsg};
-use cw20::Cw20ExecuteMsg;
-use cw_ownable::initialize_owner;
-use crate::msg::{
-    ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg, 
-    RewardResponse, StakeResponse, TotalStakedResponse,
-};
-use crate::error::ContractError;
-use crate::state::{Config, StakeInfo, CONFIG, STAKES, TOTAL_STAKED};
-
-#[entry_point]
-pub fn instantiate (
-    deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
-    msg: InstantiateMsg,
-) -> Result<Response, ContractError> {
-    initialize_owner(deps.storage, deps.api, &msg.owner)?;
-    let token = deps.api.addr_validate(&msg.token_address)?;
-
-    let config = Config{
-        token_address: token,
-        apr: msg.reward_rate,
-        lockup_period: msg.lockup_period,
-    };
-
-    CONFIG.save(deps.storage, &config)?;
-
-    TOTAL_STAKED.save(deps.storage, &Uint128::zero())?;
-
-    Ok(Response::new()
-        .add_attribute("action", "instantiate"))
-}
-
-#[entry_point]
-pub fn execute (
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    msg: ExecuteMsg,
-) -> Result<Response, ContractError> {
-    match msg {
-        ExecuteMsg::UpdateOwnership(action) => {
-            cw_ownable::update_ownership(deps, &env.block, &info.sender, action)?;
-            Ok(Response::new().add_attribute("action", "update_ownership"))
-        }
-        ExecuteMsg::Stake {amount} => {
-            execute_stake(deps, env, info, amount)
-        }
-        ExecuteMsg::Unstake {amount} => {
-            execute_unstake(deps, env, info, amount)
-        }
-        ExecuteMsg::ClaimRewards {} => {
-            execute_claim_rewards(deps, env, info)
-        }
-        ExecuteMsg::ChangeConfig { new_apr, new_lockup_period } => {
-            execute_change_config(deps, info, new_apr, new_lockup_period)
-        }
-    }
-}
-
-#[entry_point]
-pub fn query (
-    deps: Deps,
-    env: Env,
-    msg: QueryMsg,
-) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::Ownership {} => {
-            to_json_binary(&cw_ownable::get_ownership(deps.storage)?)
-        }
-        QueryMsg::Stake{address} => to_json_binary(&query_stake(deps, env, address)?),
-        QueryMsg::Config{} => to_json_binary(&query_config(deps)?),
-        QueryMsg::Reward {address} => to_json_binary(&query_reward(deps, env, address)?),
-        QueryMsg::TotalStaked {} => to_json_binary(&query_total_staked(deps)?),
-    }
-}
-
-pub fn execute_stake(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    amount: Uint128,
-) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-
-    let transfer_msg = CosmosMsg::Wasm(WasmMsg:: Execute {
-        contract_addr: config.token_address.to_string(),
-        msg: to_json_binary(&Cw20ExecuteMsg::TransferFrom{
-            owner: info.sender.to_string(),
-            recipient: env.contract.address.to_string(),
-            amount,
-        })?,
-        funds: vec![],
-    });
-
-    STAKES.update(deps.storage, &info.sender, |stake| -> StdResult<_> {
-        match stake {
-            Some(mut s) => {
-                s.amount += amount;
-                Ok(s)
-            }
-            None => Ok(StakeInfo {
-                amount: amount,
-                stake_time: env.block.time,
-            }),
-        }
-    })?;
-
-    TOTAL_STAKED.update(deps.storage, |total| -> StdResult<_> {
-        Ok(total + amount)
-    })?;
-
-    Ok(Response::new()
-        .add_message(transfer_msg)
-        .add_attribute("action", "stake")
-        .add_attribute("from", info.sender)
-        .add_attribute("amount", amount))
-}
-
-pub fn execute_unstake(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    amount: Uint128,
-) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-    let stake_info = STAKES.load(deps.storage, &info.sender)?;
-
-    if stake_info.amount < amount{
-        return Err(ContractError::InsufficientStake {});
-    }
-
-    let staked_time = env.block.time.seconds() - stake_info.stake_time.seconds();
-    if staked_time < config.lockup_period {
-        return Err(ContractError::LockupNotExpired {});
-    }
-
-    let reward_amount = calculate_reward_apr(stake_info.amount, staked_time, config.apr);
-    let mut messages = vec![];
-
-    if !reward_amount.is_zero() {
-        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: config.token_address.to_string(),
-            msg: to_json_binary(&Cw20ExecuteMsg::Mint { 
-                recipient: info.sender.to_string(),
-                amount: reward_amount,
-            })?,
-            funds: vec![],
-        }));
-    }
-
-    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: config.token_address.to_string(),
-        msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
-            recipient: info.sender.to_string(),
-            amount: amount,
-        })?,
-        funds: vec![],
-    }));
-
-    let staking_remain = stake_info.amount - amount;
-    if staking_remain.is_zero() {
-        STAKES.remove(deps.storage, &info.sender);
-    } else {
-        STAKES.save(deps.storage, &info.sender, &StakeInfo {
-            amount: staking_remain,
-            stake_time: env.block.time,
-        })?;
-    }
-
-    TOTAL_STAKED.update(deps.storage, |total| -> StdResult<_> {
-        Ok(total.checked_sub(amount)?)
-    })?;
-
-    Ok(Response::new()
-        .add_messages(messages)
-        .add_attribute("action", "unstake")
-        .add_attribute("to", info.sender)
-        .add_attribute("amount", amount)
-        .add_attribute("reward", reward_amount))
-}
-
-pub fn execute_claim_rewards(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-    let stake_info = STAKES.load(deps.storage, &info.sender)?;
-
-    let staked_time = env.block.time.seconds() - stake_info.stake_time.seconds();
-    if staked_time < config.lockup_period {
-        return Err(ContractError::LockupNotExpired {});
-    }
-
-    let reward_amount = calculate_reward_apr(stake_info.amount, staked_time, config.apr);
-    if reward_amount.is_zero() {
-        return Err(ContractError::ZeroReward {});
-    }
-
-    STAKES.save(deps.storage, &info.sender, &StakeInfo{
-        amount: stake_info.amount,
-        stake_time: env.block.time,
-    })?;
-
-    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: config.token_address.to_string(),
-        msg: to_json_binary(&Cw20ExecuteMsg::Mint { 
-            recipient: info.sender.to_string(),
-            amount: reward_amount,
-        })?,
-        funds: vec![],
-    });
-
-    Ok(Response::new()
-        .add_message(msg)
-        .add_attribute("action", "claim_rewards")
-        .add_attribute("to", info.sender)
-        .add_attribute("reward", reward_amount))
-}
-
-
-pub fn execute_change_config(
-    deps: DepsMut,
-    info: MessageInfo,
-    new_apr: u64,
-    new_lockup_period: u64,
-) -> Result<Response, ContractError> {
-    cw_ownable::assert_owner(deps.storage, &info.sender)?;
-    let curr_config = CONFIG.load(deps.storage)?;
-
-    let config = Config {
-        token_address: curr_config.token_address,
-        apr: new_apr,
-        lockup_period: new_lockup_period,
-    };
-
-    CONFIG.save(deps.storage, &config)?;
-
-    Ok(Response::new()
-        .add_attribute("action", "change_config")
-        .add_attribute("new_apr", new_apr.to_string())
-        .add_attribute("new_lockup_period", new_lockup_period.to_string()))
-}
-
-fn calculate_reward_apr(stake_amount: Uint128, staked_seconds: u64, annual_rate_bps: u64) -> Uint128 {
-    if stake_amount.is_zero() || staked_seconds == 0 || annual_rate_bps == 0 {
-        return Uint128::zero();
-    }
-
-    const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
-    
-    let stake = stake_amount.u128();
-    let rate = annual_rate_bps as u128;
-    let time = staked_seconds as u128;
-    
-    let reward = stake
-        .checked_mul(rate).unwrap()
-        .checked_mul(time).unwrap()
-        .checked_div(10_000).unwrap()
-        .checked_div(SECONDS_PER_YEAR as u128).unwrap();
-    
-    Uint128::from(reward)
-}
-
-fn query_config(
-    deps: Deps,
-) -> StdResult<ConfigResponse> {
-    let config = CONFIG.load(deps.storage)?;
-    Ok(ConfigResponse {
-        token_address: config.token_address.to_string(),
-        apr: config.apr,
-        lockup_period: config.lockup_period,
-    })
-}
-
-fn query_reward(
-    deps: Deps,
-    env: Env,
-    address: String,
-) -> StdResult<RewardResponse> {
-    let addr = deps.api.addr_validate(&address)?;
-    let config = CONFIG.load(deps.storage)?;
-    let stake_info = STAKES.may_load(deps.storage, &addr)?;
-
-    let rewards = match stake_info {
-        Some(s) => {
-            let staked_time = env.block.time.seconds() - s.stake_time.seconds();
-            calculate_reward_apr(s.amount, staked_time, config.apr)
-        }
-        None => Uint128::zero()
-    };
-
-    Ok(RewardResponse{
-        amount: rewards,
-    })
-}
-
-fn query_stake(
-    deps: Deps,
-    _env: Env,
-    address: String
-) -> StdResult<StakeResponse> {
-    let addr = deps.api.addr_validate(&address)?;
-    let stake_info = STAKES.may_load(deps.storage, &addr)?;
-
-    match stake_info {
-        Some(s) => Ok(StakeResponse {
-            amount: s.amount,
-            stake_time: s.stake_time.seconds(),
-        }),
-        None => Ok(StakeResponse {
-            amount: Uint128::zero(),
-            stake_time: 0,
-        })
-    }
-}
-
-fn query_total_staked(
-    deps: Deps
-) -> StdResult<TotalStakedResponse> {
-    Ok(TotalStakedResponse{
-        total: TOTAL_STAKED.load(deps.storage)?,
-    })
+use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, entry_point, to_json_binary, Uint128, StdResult, StdError, WasmMsg, CosmosMsg, Addr, Empty, Reply, SubMsg, Decimal, Storage, Order};
+use cw_storage_plus::Bound;
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
+use cw_ownable::initialize_owner;
+use shared::parse_reply_instantiate_data;
+use crate::msg::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg, ReceiptPoolResponse, ReceiptTokenMsg,
+    RewardResponse, SlashHistoryResponse, SlashResponse, StakeResponse, TotalStakedResponse,
+    UnbondingEntry, UnbondingResponse, VestingResponse, WhitelistedResponse,
+};
+use crate::error::ContractError;
+use crate::state::{
+    Config, ReceiptPool, RewardVesting, SlashRecord, StakeInfo, Unbonding, VestingSchedule,
+    CONFIG, RECEIPT_POOL, SLASHES, SLASH_COUNT, STAKES, TOTAL_STAKED, UNBONDING,
+    UNBONDING_COUNT, VESTING, VESTING_COUNT, WHITELIST,
+};
+
+const INSTANTIATE_RECEIPT_REPLY_ID: u64 = 1;
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+// bound the per-address loops over VESTING and UNBONDING
+const MAX_VESTING_TRANCHES: usize = 30;
+const MAX_UNBONDING_ENTRIES: usize = 30;
+
+#[entry_point]
+pub fn instantiate (
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    initialize_owner(deps.storage, deps.api, Some(&msg.owner))?;
+    let token = deps.api.addr_validate(&msg.token_address)?;
+    let receipt_token = match &msg.receipt_token {
+        Some(ReceiptTokenMsg::Existing { address }) => Some(deps.api.addr_validate(address)?),
+        _ => None,
+    };
+    if let Some(vesting) = &msg.reward_vesting {
+        validate_vesting(vesting)?;
+    }
+    let slasher = msg.slasher
+        .map(|s| deps.api.addr_validate(&s))
+        .transpose()?;
+    let slash_recipient = msg.slash_recipient
+        .map(|r| deps.api.addr_validate(&r))
+        .transpose()?;
+
+    let config = Config{
+        token_address: token,
+        apr: msg.reward_rate,
+        lockup_period: msg.lockup_period,
+        max_total_stake: msg.max_total_stake,
+        max_stake_per_address: msg.max_stake_per_address,
+        whitelist_enabled: msg.whitelist.is_some(),
+        receipt_token,
+        reward_vesting: msg.reward_vesting,
+        slasher,
+        slash_recipient,
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+
+    for address in msg.whitelist.unwrap_or_default() {
+        let addr = deps.api.addr_validate(&address)?;
+        WHITELIST.save(deps.storage, &addr, &Empty {})?;
+    }
+
+    TOTAL_STAKED.save(deps.storage, &Uint128::zero())?;
+    RECEIPT_POOL.save(deps.storage, &ReceiptPool {
+        total_underlying: Uint128::zero(),
+        total_supply: Uint128::zero(),
+        last_compound: env.block.time,
+    })?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "instantiate");
+
+    if let Some(ReceiptTokenMsg::New { code_id, name, symbol, decimals }) = msg.receipt_token {
+        let instantiate_msg = WasmMsg::Instantiate {
+            admin: None,
+            code_id,
+            msg: to_json_binary(&cw20_token::msg::InstantiateMsg {
+                name: name.clone(),
+                symbol,
+                decimals,
+                initial_balances: vec![],
+                owner: None,
+                minter: Some(env.contract.address.to_string()),
+                cap: None,
+                marketing: None,
+                // lets this contract, the owner, burn slashed receipts
+                compliance: true,
+                transfer_fee_bps: None,
+                fee_recipient: None,
+            })?,
+            funds: vec![],
+            label: name,
+        };
+        response = response.add_submessage(
+            SubMsg::reply_on_success(instantiate_msg, INSTANTIATE_RECEIPT_REPLY_ID),
+        );
+    }
+
+    Ok(response)
+}
+
+#[entry_point]
+pub fn reply (
+    deps: DepsMut,
+    _env: Env,
+    msg: Reply,
+) -> Result<Response, ContractError> {
+    match msg.id {
+        INSTANTIATE_RECEIPT_REPLY_ID => {
+            let res = parse_reply_instantiate_data(msg)?;
+            let receipt_token = deps.api.addr_validate(&res.contract_address)?;
+
+            CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+                config.receipt_token = Some(receipt_token.clone());
+                Ok(config)
+            })?;
+
+            Ok(Response::new()
+                .add_attribute("action", "set_receipt_token")
+                .add_attribute("receipt_token", receipt_token))
+        }
+        id => Err(ContractError::UnknownReplyId { id }),
+    }
+}
+
+#[entry_point]
+pub fn execute (
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::UpdateOwnership(action) => {
+            cw_ownable::update_ownership(deps, &env.block, &info.sender, action)?;
+            Ok(Response::new().add_attribute("action", "update_ownership"))
+        }
+        ExecuteMsg::Stake {amount} => {
+            execute_stake(deps, env, info, amount)
+        }
+        ExecuteMsg::Unstake {amount} => {
+            execute_unstake(deps, env, info, amount)
+        }
+        ExecuteMsg::ClaimRewards {} => {
+            execute_claim_rewards(deps, env, info)
+        }
+        ExecuteMsg::WithdrawVested {} => {
+            execute_withdraw_vested(deps, env, info)
+        }
+        ExecuteMsg::UpdateRewardVesting { reward_vesting } => {
+            execute_update_reward_vesting(deps, info, reward_vesting)
+        }
+        ExecuteMsg::Slash { address, fraction, reason } => {
+            execute_slash(deps, env, info, address, fraction, reason)
+        }
+        ExecuteMsg::UpdateSlashing { slasher, slash_recipient } => {
+            execute_update_slashing(deps, info, slasher, slash_recipient)
+        }
+        ExecuteMsg::LiquidStake {amount} => {
+            execute_liquid_stake(deps, env, info, amount)
+        }
+        ExecuteMsg::LiquidUnstake {amount} => {
+            execute_liquid_unstake(deps, env, info, amount)
+        }
+        ExecuteMsg::WithdrawUnbonded {} => {
+            execute_withdraw_unbonded(deps, env, info)
+        }
+        ExecuteMsg::ChangeConfig { new_apr, new_lockup_period } => {
+            execute_change_config(deps, info, new_apr, new_lockup_period)
+        }
+        ExecuteMsg::UpdateStakeLimits { max_total_stake, max_stake_per_address } => {
+            execute_update_stake_limits(deps, info, max_total_stake, max_stake_per_address)
+        }
+        ExecuteMsg::UpdateWhitelist { add, remove } => {
+            execute_update_whitelist(deps, info, add, remove)
+        }
+        ExecuteMsg::SetWhitelistPhase { enabled } => {
+            execute_set_whitelist_phase(deps, info, enabled)
+        }
+    }
+}
+
+#[entry_point]
+pub fn query (
+    deps: Deps,
+    env: Env,
+    msg: QueryMsg,
+) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Ownership {} => {
+            to_json_binary(&cw_ownable::get_ownership(deps.storage)?)
+        }
+        QueryMsg::Stake{address} => to_json_binary(&query_stake(deps, env, address)?),
+        QueryMsg::Config{} => to_json_binary(&query_config(deps)?),
+        QueryMsg::Reward {address} => to_json_binary(&query_reward(deps, env, address)?),
+        QueryMsg::TotalStaked {} => to_json_binary(&query_total_staked(deps)?),
+        QueryMsg::Whitelisted {address} => to_json_binary(&query_whitelisted(deps, address)?),
+        QueryMsg::ReceiptPool {} => to_json_binary(&query_receipt_pool(deps, env)?),
+        QueryMsg::Vesting {address} => to_json_binary(&query_vesting(deps, env, address)?),
+        QueryMsg::Unbonding { address, start_after, limit } => {
+            to_json_binary(&query_unbonding(deps, address, start_after, limit)?)
+        }
+        QueryMsg::SlashHistory { address, start_after, limit } => {
+            to_json_binary(&query_slash_history(deps, address, start_after, limit)?)
+        }
+    }
+}
+
+pub fn execute_stake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let staked = staked_by(deps.as_ref(), &config, &info.sender)?;
+    assert_can_stake(deps.as_ref(), &config, &info.sender, staked, amount)?;
+
+    let transfer_msg = CosmosMsg::Wasm(WasmMsg:: Execute {
+        contract_addr: config.token_address.to_string(),
+        msg: to_json_binary(&Cw20ExecuteMsg::TransferFrom{
+            owner: info.sender.to_string(),
+            recipient: env.contract.address.to_string(),
+            amount,
+        })?,
+        funds: vec![],
+    });
+
+    STAKES.update(deps.storage, &info.sender, |stake| -> StdResult<_> {
+        match stake {
+            Some(mut s) => {
+                s.amount += amount;
+                Ok(s)
+            }
+            None => Ok(StakeInfo {
+                amount,
+                stake_time: env.block.time,
+            }),
+        }
+    })?;
+
+    TOTAL_STAKED.update(deps.storage, |total| -> StdResult<_> {
+        Ok(total + amount)
+    })?;
+
+    Ok(Response::new()
+        .add_message(transfer_msg)
+        .add_attribute("action", "stake")
+        .add_attribute("from", info.sender)
+        .add_attribute("amount", amount))
+}
+
+pub fn execute_unstake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let stake_info = STAKES.load(deps.storage, &info.sender)?;
+
+    if stake_info.amount < amount{
+        return Err(ContractError::InsufficientStake {});
+    }
+
+    let staked_time = env.block.time.seconds() - stake_info.stake_time.seconds();
+    if staked_time < config.lockup_period {
+        return Err(ContractError::LockupNotExpired {});
+    }
+
+    let reward_amount = calculate_reward_apr(stake_info.amount, staked_time, config.apr);
+    let mut messages = vec![];
+
+    if !reward_amount.is_zero() {
+        messages.extend(pay_reward(deps.storage, &env, &config, &info.sender, reward_amount)?);
+    }
+
+    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: config.token_address.to_string(),
+        msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: info.sender.to_string(),
+            amount,
+        })?,
+        funds: vec![],
+    }));
+
+    let staking_remain = stake_info.amount - amount;
+    if staking_remain.is_zero() {
+        STAKES.remove(deps.storage, &info.sender);
+    } else {
+        STAKES.save(deps.storage, &info.sender, &StakeInfo {
+            amount: staking_remain,
+            stake_time: env.block.time,
+        })?;
+    }
+
+    TOTAL_STAKED.update(deps.storage, |total| -> StdResult<_> {
+        Ok(total.checked_sub(amount)?)
+    })?;
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "unstake")
+        .add_attribute("to", info.sender)
+        .add_attribute("amount", amount)
+        .add_attribute("reward", reward_amount))
+}
+
+pub fn execute_claim_rewards(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let stake_info = STAKES.load(deps.storage, &info.sender)?;
+
+    let staked_time = env.block.time.seconds() - stake_info.stake_time.seconds();
+    if staked_time < config.lockup_period {
+        return Err(ContractError::LockupNotExpired {});
+    }
+
+    let reward_amount = calculate_reward_apr(stake_info.amount, staked_time, config.apr);
+    if reward_amount.is_zero() {
+        return Err(ContractError::ZeroReward {});
+    }
+
+    STAKES.save(deps.storage, &info.sender, &StakeInfo{
+        amount: stake_info.amount,
+        stake_time: env.block.time,
+    })?;
+
+    let messages = pay_reward(deps.storage, &env, &config, &info.sender, reward_amount)?;
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "claim_rewards")
+        .add_attribute("to", info.sender)
+        .add_attribute("reward", reward_amount))
+}
+
+pub fn execute_withdraw_vested(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let amount = release_vested(deps.storage, &env, &info.sender)?;
+    if amount.is_zero() {
+        return Err(ContractError::NothingToWithdraw {});
+    }
+
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: config.token_address.to_string(),
+        msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: info.sender.to_string(),
+            amount,
+        })?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "withdraw_vested")
+        .add_attribute("to", info.sender)
+        .add_attribute("amount", amount))
+}
+
+/// Mints `amount` of rewards to `recipient`, or into this contract as a new
+/// vesting tranche starting now when reward vesting is enabled. Tranches from
+/// earlier claims keep vesting on their own schedule.
+///
+/// An address holds at most `MAX_VESTING_TRANCHES` tranches. At the limit the
+/// vested part of every tranche is paid out first, and if none of them is done
+/// the reward is folded into the newest tranche, which restarts with it.
+fn pay_reward(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    recipient: &Addr,
+    amount: Uint128,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let vesting = match &config.reward_vesting {
+        Some(vesting) => vesting,
+        None => {
+            return Ok(vec![CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: config.token_address.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Mint {
+                    recipient: recipient.to_string(),
+                    amount,
+                })?,
+                funds: vec![],
+            })]);
+        }
+    };
+
+    let mut messages = vec![CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: config.token_address.to_string(),
+        msg: to_json_binary(&Cw20ExecuteMsg::Mint {
+            recipient: env.contract.address.to_string(),
+            amount,
+        })?,
+        funds: vec![],
+    })];
+
+    let mut schedule = VestingSchedule {
+        total: amount,
+        released: Uint128::zero(),
+        start: env.block.time,
+        cliff: vesting.cliff,
+        duration: vesting.duration,
+    };
+
+    let mut tranches = vesting_tranches(storage, recipient)?;
+    if tranches.len() >= MAX_VESTING_TRANCHES {
+        let released = release_vested(storage, env, recipient)?;
+        if !released.is_zero() {
+            messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: config.token_address.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount: released,
+                })?,
+                funds: vec![],
+            }));
+        }
+        tranches = vesting_tranches(storage, recipient)?;
+    }
+
+    match tranches.last() {
+        Some((id, newest)) if tranches.len() >= MAX_VESTING_TRANCHES => {
+            schedule.total += newest.total - newest.released;
+            VESTING.save(storage, (recipient, *id), &schedule)?;
+        }
+        _ => {
+            let id = VESTING_COUNT.may_load(storage, recipient)?.unwrap_or_default();
+            VESTING_COUNT.save(storage, recipient, &(id + 1))?;
+            VESTING.save(storage, (recipient, id), &schedule)?;
+        }
+    }
+
+    Ok(messages)
+}
+
+fn vesting_tranches(storage: &dyn Storage, addr: &Addr) -> StdResult<Vec<(u64, VestingSchedule)>> {
+    VESTING
+        .prefix(addr)
+        .range(storage, None, None, Order::Ascending)
+        .collect()
+}
+
+/// Marks everything vested so far as released and drops the finished
+/// tranches, returning the amount to pay out
+fn release_vested(storage: &mut dyn Storage, env: &Env, addr: &Addr) -> StdResult<Uint128> {
+    let mut amount = Uint128::zero();
+    for (id, mut schedule) in vesting_tranches(storage, addr)? {
+        let releasable = schedule.vested(env.block.time) - schedule.released;
+        if releasable.is_zero() {
+            continue;
+        }
+
+        amount += releasable;
+        schedule.released += releasable;
+        if schedule.released == schedule.total {
+            VESTING.remove(storage, (addr, id));
+        } else {
+            VESTING.save(storage, (addr, id), &schedule)?;
+        }
+    }
+    Ok(amount)
+}
+
+pub fn execute_slash(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: String,
+    fraction: Decimal,
+    reason: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.slasher.as_ref() != Some(&info.sender) {
+        cw_ownable::assert_owner(deps.storage, &info.sender)
+            .map_err(|_| ContractError::Unauthorized {})?;
+    }
+
+    if fraction.is_zero() || fraction > Decimal::one() {
+        return Err(ContractError::InvalidSlashFraction {});
+    }
+
+    let addr = deps.api.addr_validate(&address)?;
+    let stake_info = STAKES.may_load(deps.storage, &addr)?;
+    let receipts = receipts_held(deps.as_ref(), &config, &addr)?;
+    let unbonding = UNBONDING
+        .prefix(&addr)
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    if stake_info.is_none() && receipts.is_zero() && unbonding.is_empty() {
+        return Err(ContractError::NoStake {});
+    }
+
+    // compound first so the slash comes out of an up to date pool
+    let mut messages = compound_receipt_pool(deps.storage, &env, &config)?;
+    let mut pool = RECEIPT_POOL.load(deps.storage)?;
+
+    let direct_amount = stake_info.as_ref()
+        .map(|s| s.amount.mul_floor(fraction))
+        .unwrap_or_default();
+    let seized_receipts = receipts.mul_floor(fraction);
+    let liquid_amount = receipts_to_underlying(&pool, seized_receipts);
+    let unbonding_amount = unbonding.iter()
+        .map(|(_, entry)| entry.amount.mul_floor(fraction))
+        .sum::<Uint128>();
+    let amount = direct_amount + liquid_amount + unbonding_amount;
+    if amount.is_zero() {
+        return Err(ContractError::ZeroSlashAmount {});
+    }
+
+    if let Some(mut stake_info) = stake_info {
+        stake_info.amount -= direct_amount;
+        if stake_info.amount.is_zero() {
+            STAKES.remove(deps.storage, &addr);
+        } else {
+            STAKES.save(deps.storage, &addr, &stake_info)?;
+        }
+        TOTAL_STAKED.update(deps.storage, |total| -> StdResult<_> {
+            Ok(total.checked_sub(direct_amount)?)
+        })?;
+    }
+
+    // the slashed receipts are burned with their share of the pool, so the
+    // exchange rate of everyone else's receipts stays where it was
+    if !seized_receipts.is_zero() {
+        pool.total_supply = pool.total_supply.checked_sub(seized_receipts).map_err(StdError::from)?;
+        pool.total_underlying -= liquid_amount;
+        RECEIPT_POOL.save(deps.storage, &pool)?;
+
+        let receipt_token = config.receipt_token.as_ref().ok_or(ContractError::ReceiptTokenNotSet {})?;
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: receipt_token.to_string(),
+            msg: to_json_binary(&cw20_token::msg::ExecuteMsg::ForceBurn {
+                address: addr.to_string(),
+                amount: seized_receipts,
+            })?,
+            funds: vec![],
+        }));
+    }
+
+    for (id, mut entry) in unbonding {
+        entry.amount -= entry.amount.mul_floor(fraction);
+        if entry.amount.is_zero() {
+            UNBONDING.remove(deps.storage, (&addr, id));
+        } else {
+            UNBONDING.save(deps.storage, (&addr, id), &entry)?;
+        }
+    }
+
+    let id = SLASH_COUNT.may_load(deps.storage, &addr)?.unwrap_or_default();
+    SLASH_COUNT.save(deps.storage, &addr, &(id + 1))?;
+    SLASHES.save(deps.storage, (&addr, id), &SlashRecord {
+        amount,
+        fraction,
+        reason: reason.clone(),
+        height: env.block.height,
+        time: env.block.time,
+    })?;
+
+    let msg = match &config.slash_recipient {
+        Some(recipient) => Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount,
+        },
+        None => Cw20ExecuteMsg::Burn { amount },
+    };
+
+    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: config.token_address.to_string(),
+        msg: to_json_binary(&msg)?,
+        funds: vec![],
+    }));
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "slash")
+        .add_attribute("address", addr)
+        .add_attribute("amount", amount)
+        .add_attribute("liquid_amount", liquid_amount)
+        .add_attribute("unbonding_amount", unbonding_amount)
+        .add_attribute("fraction", fraction.to_string())
+        .add_attribute("reason", reason))
+}
+
+pub fn execute_liquid_stake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let receipt_token = config.receipt_token.clone().ok_or(ContractError::ReceiptTokenNotSet {})?;
+
+    let mut messages = compound_receipt_pool(deps.storage, &env, &config)?;
+    let staked = staked_by(deps.as_ref(), &config, &info.sender)?;
+    assert_can_stake(deps.as_ref(), &config, &info.sender, staked, amount)?;
+    let mut pool = RECEIPT_POOL.load(deps.storage)?;
+
+    // unbacked receipts would take a share of the new deposit
+    if !pool.total_supply.is_zero() && pool.total_underlying.is_zero() {
+        return Err(ContractError::InsolventReceiptPool {});
+    }
+
+    let receipts = if pool.total_supply.is_zero() {
+        amount
+    } else {
+        amount.multiply_ratio(pool.total_supply, pool.total_underlying)
+    };
+    if receipts.is_zero() {
+        return Err(ContractError::ZeroReceiptAmount {});
+    }
+
+    pool.total_underlying += amount;
+    pool.total_supply += receipts;
+    RECEIPT_POOL.save(deps.storage, &pool)?;
+
+    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: config.token_address.to_string(),
+        msg: to_json_binary(&Cw20ExecuteMsg::TransferFrom {
+            owner: info.sender.to_string(),
+            recipient: env.contract.address.to_string(),
+            amount,
+        })?,
+        funds: vec![],
+    }));
+    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: receipt_token.to_string(),
+        msg: to_json_binary(&Cw20ExecuteMsg::Mint {
+            recipient: info.sender.to_string(),
+            amount: receipts,
+        })?,
+        funds: vec![],
+    }));
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "liquid_stake")
+        .add_attribute("from", info.sender)
+        .add_attribute("amount", amount)
+        .add_attribute("receipts", receipts))
+}
+
+pub fn execute_liquid_unstake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let receipt_token = config.receipt_token.clone().ok_or(ContractError::ReceiptTokenNotSet {})?;
+
+    let mut messages = compound_receipt_pool(deps.storage, &env, &config)?;
+    let mut pool = RECEIPT_POOL.load(deps.storage)?;
+
+    if amount > pool.total_supply {
+        return Err(ContractError::InsufficientStake {});
+    }
+    let underlying = receipts_to_underlying(&pool, amount);
+    if underlying.is_zero() {
+        return Err(ContractError::ZeroReceiptAmount {});
+    }
+
+    pool.total_underlying -= underlying;
+    pool.total_supply -= amount;
+    RECEIPT_POOL.save(deps.storage, &pool)?;
+
+    // entries are bounded so withdrawing and slashing them stays cheap, at
+    // the limit the redemption joins the newest entry and pushes out its release
+    let release_at = env.block.time.plus_seconds(config.lockup_period);
+    let mut entry = Unbonding {
+        amount: underlying,
+        release_at,
+    };
+    let entries = UNBONDING
+        .prefix(&info.sender)
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    match entries.last() {
+        Some((id, newest)) if entries.len() >= MAX_UNBONDING_ENTRIES => {
+            entry.amount += newest.amount;
+            UNBONDING.save(deps.storage, (&info.sender, *id), &entry)?;
+        }
+        _ => {
+            let id = UNBONDING_COUNT.may_load(deps.storage, &info.sender)?.unwrap_or_default();
+            UNBONDING_COUNT.save(deps.storage, &info.sender, &(id + 1))?;
+            UNBONDING.save(deps.storage, (&info.sender, id), &entry)?;
+        }
+    }
+
+    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: receipt_token.to_string(),
+        msg: to_json_binary(&Cw20ExecuteMsg::TransferFrom {
+            owner: info.sender.to_string(),
+            recipient: env.contract.address.to_string(),
+            amount,
+        })?,
+        funds: vec![],
+    }));
+    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: receipt_token.to_string(),
+        msg: to_json_binary(&Cw20ExecuteMsg::Burn { amount })?,
+        funds: vec![],
+    }));
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "liquid_unstake")
+        .add_attribute("to", info.sender)
+        .add_attribute("receipts", amount)
+        .add_attribute("amount", underlying)
+        .add_attribute("release_at", release_at.seconds().to_string()))
+}
+
+pub fn execute_withdraw_unbonded(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let entries = UNBONDING
+        .prefix(&info.sender)
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut amount = Uint128::zero();
+    for (id, entry) in entries {
+        if entry.release_at <= env.block.time {
+            amount += entry.amount;
+            UNBONDING.remove(deps.storage, (&info.sender, id));
+        }
+    }
+
+    if amount.is_zero() {
+        return Err(ContractError::NothingToWithdraw {});
+    }
+
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: config.token_address.to_string(),
+        msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: info.sender.to_string(),
+            amount,
+        })?,
+        funds: vec![],
+    });
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "withdraw_unbonded")
+        .add_attribute("to", info.sender)
+        .add_attribute("amount", amount))
+}
+
+/// Mints the rewards accrued by the receipt pool since the last compound
+/// back into the pool, which is what grows the receipt exchange rate.
+fn compound_receipt_pool(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let mut pool = RECEIPT_POOL.load(storage)?;
+    let reward = pending_receipt_reward(&pool, env, config.apr);
+
+    pool.total_underlying += reward;
+    pool.last_compound = env.block.time;
+    RECEIPT_POOL.save(storage, &pool)?;
+
+    if reward.is_zero() {
+        return Ok(vec![]);
+    }
+
+    Ok(vec![CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: config.token_address.to_string(),
+        msg: to_json_binary(&Cw20ExecuteMsg::Mint {
+            recipient: env.contract.address.to_string(),
+            amount: reward,
+        })?,
+        funds: vec![],
+    })])
+}
+
+fn pending_receipt_reward(pool: &ReceiptPool, env: &Env, apr: u64) -> Uint128 {
+    let elapsed = env.block.time.seconds() - pool.last_compound.seconds();
+    calculate_reward_apr(pool.total_underlying, elapsed, apr)
+}
+
+pub fn execute_change_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_apr: u64,
+    new_lockup_period: u64,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    let curr_config = CONFIG.load(deps.storage)?;
+
+    let config = Config {
+        apr: new_apr,
+        lockup_period: new_lockup_period,
+        ..curr_config
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "change_config")
+        .add_attribute("new_apr", new_apr.to_string())
+        .add_attribute("new_lockup_period", new_lockup_period.to_string()))
+}
+
+pub fn execute_update_stake_limits(
+    deps: DepsMut,
+    info: MessageInfo,
+    max_total_stake: Option<Uint128>,
+    max_stake_per_address: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.max_total_stake = max_total_stake;
+        config.max_stake_per_address = max_stake_per_address;
+        Ok(config)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_stake_limits")
+        .add_attribute("max_total_stake", format_limit(max_total_stake))
+        .add_attribute("max_stake_per_address", format_limit(max_stake_per_address)))
+}
+
+pub fn execute_update_reward_vesting(
+    deps: DepsMut,
+    info: MessageInfo,
+    reward_vesting: Option<RewardVesting>,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    if let Some(vesting) = &reward_vesting {
+        validate_vesting(vesting)?;
+    }
+
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.reward_vesting = reward_vesting.clone();
+        Ok(config)
+    })?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "update_reward_vesting")
+        .add_attribute("enabled", reward_vesting.is_some().to_string());
+    if let Some(vesting) = reward_vesting {
+        response = response
+            .add_attribute("cliff", vesting.cliff.to_string())
+            .add_attribute("duration", vesting.duration.to_string());
+    }
+
+    Ok(response)
+}
+
+pub fn execute_update_slashing(
+    deps: DepsMut,
+    info: MessageInfo,
+    slasher: Option<String>,
+    slash_recipient: Option<String>,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let slasher = slasher
+        .map(|s| deps.api.addr_validate(&s))
+        .transpose()?;
+    let slash_recipient = slash_recipient
+        .map(|r| deps.api.addr_validate(&r))
+        .transpose()?;
+
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.slasher = slasher.clone();
+        config.slash_recipient = slash_recipient.clone();
+        Ok(config)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_slashing")
+        .add_attribute("slasher", slasher.map(|s| s.to_string()).unwrap_or_else(|| "none".to_string()))
+        .add_attribute("slash_recipient", slash_recipient.map(|r| r.to_string()).unwrap_or_else(|| "burn".to_string())))
+}
+
+pub fn execute_update_whitelist(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    for address in &add {
+        let addr = deps.api.addr_validate(address)?;
+        WHITELIST.save(deps.storage, &addr, &Empty {})?;
+    }
+    for address in &remove {
+        let addr = deps.api.addr_validate(address)?;
+        WHITELIST.remove(deps.storage, &addr);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "update_whitelist")
+        .add_attribute("added", add.len().to_string())
+        .add_attribute("removed", remove.len().to_string()))
+}
+
+pub fn execute_set_whitelist_phase(
+    deps: DepsMut,
+    info: MessageInfo,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.whitelist_enabled = enabled;
+        Ok(config)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_whitelist_phase")
+        .add_attribute("enabled", enabled.to_string()))
+}
+
+/// Checks the whitelist phase and the stake caps for a deposit of `amount`.
+/// `staked` is the principal the address already has, see [`staked_by`].
+fn assert_can_stake(
+    deps: Deps,
+    config: &Config,
+    staker: &Addr,
+    staked: Uint128,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    if config.whitelist_enabled && !WHITELIST.has(deps.storage, staker) {
+        return Err(ContractError::NotWhitelisted {});
+    }
+
+    if let Some(max) = config.max_stake_per_address {
+        if staked + amount > max {
+            return Err(ContractError::MaxStakePerAddressExceeded { max });
+        }
+    }
+
+    if let Some(max) = config.max_total_stake {
+        let total = TOTAL_STAKED.load(deps.storage)? + RECEIPT_POOL.load(deps.storage)?.total_underlying;
+        if total + amount > max {
+            return Err(ContractError::MaxTotalStakeExceeded { max });
+        }
+    }
+
+    Ok(())
+}
+
+/// Direct stake plus the stake behind the receipts the address holds
+fn staked_by(deps: Deps, config: &Config, addr: &Addr) -> StdResult<Uint128> {
+    let direct = STAKES.may_load(deps.storage, addr)?
+        .map(|s| s.amount)
+        .unwrap_or_default();
+    let receipts = receipts_held(deps, config, addr)?;
+    let pool = RECEIPT_POOL.load(deps.storage)?;
+    Ok(direct + receipts_to_underlying(&pool, receipts))
+}
+
+/// Receipt balance of `addr`, receipts follow transfers so the token is the source of truth
+fn receipts_held(deps: Deps, config: &Config, addr: &Addr) -> StdResult<Uint128> {
+    let receipt_token = match &config.receipt_token {
+        Some(token) => token,
+        None => return Ok(Uint128::zero()),
+    };
+    let res: BalanceResponse = deps.querier.query_wasm_smart(
+        receipt_token,
+        &Cw20QueryMsg::Balance { address: addr.to_string() },
+    )?;
+    Ok(res.balance)
+}
+
+fn receipts_to_underlying(pool: &ReceiptPool, receipts: Uint128) -> Uint128 {
+    if pool.total_supply.is_zero() {
+        return Uint128::zero();
+    }
+    receipts.multiply_ratio(pool.total_underlying, pool.total_supply)
+}
+
+fn validate_vesting(vesting: &RewardVesting) -> Result<(), ContractError> {
+    if vesting.duration == 0 || vesting.cliff > vesting.duration {
+        return Err(ContractError::InvalidVesting {});
+    }
+    Ok(())
+}
+
+fn format_limit(limit: Option<Uint128>) -> String {
+    limit.map(|l| l.to_string()).unwrap_or_else(|| "none".to_string())
+}
+
+fn calculate_reward_apr(stake_amount: Uint128, staked_seconds: u64, annual_rate_bps: u64) -> Uint128 {
+    if stake_amount.is_zero() || staked_seconds == 0 || annual_rate_bps == 0 {
+        return Uint128::zero();
+    }
+
+    const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+    
+    let stake = stake_amount.u128();
+    let rate = annual_rate_bps as u128;
+    let time = staked_seconds as u128;
+    
+    let reward = stake
+        .checked_mul(rate).unwrap()
+        .checked_mul(time).unwrap()
+        .checked_div(10_000).unwrap()
+        .checked_div(SECONDS_PER_YEAR as u128).unwrap();
+    
+    Uint128::from(reward)
+}
+
+fn query_config(
+    deps: Deps,
+) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        token_address: config.token_address.to_string(),
+        apr: config.apr,
+        lockup_period: config.lockup_period,
+        max_total_stake: config.max_total_stake,
+        max_stake_per_address: config.max_stake_per_address,
+        whitelist_enabled: config.whitelist_enabled,
+        receipt_token: config.receipt_token.map(|t| t.to_string()),
+        reward_vesting: config.reward_vesting,
+        slasher: config.slasher.map(|s| s.to_string()),
+        slash_recipient: config.slash_recipient.map(|r| r.to_string()),
+    })
+}
+
+fn query_reward(
+    deps: Deps,
+    env: Env,
+    address: String,
+) -> StdResult<RewardResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let config = CONFIG.load(deps.storage)?;
+    let stake_info = STAKES.may_load(deps.storage, &addr)?;
+
+    let rewards = match stake_info {
+        Some(s) => {
+            let staked_time = env.block.time.seconds() - s.stake_time.seconds();
+            calculate_reward_apr(s.amount, staked_time, config.apr)
+        }
+        None => Uint128::zero()
+    };
+
+    Ok(RewardResponse{
+        amount: rewards,
+    })
+}
+
+fn query_stake(
+    deps: Deps,
+    _env: Env,
+    address: String
+) -> StdResult<StakeResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let stake_info = STAKES.may_load(deps.storage, &addr)?;
+
+    match stake_info {
+        Some(s) => Ok(StakeResponse {
+            amount: s.amount,
+            stake_time: s.stake_time.seconds(),
+        }),
+        None => Ok(StakeResponse {
+            amount: Uint128::zero(),
+            stake_time: 0,
+        })
+    }
+}
+
+fn query_total_staked(
+    deps: Deps
+) -> StdResult<TotalStakedResponse> {
+    Ok(TotalStakedResponse{
+        total: TOTAL_STAKED.load(deps.storage)?,
+    })
+}
+
+fn query_whitelisted(
+    deps: Deps,
+    address: String,
+) -> StdResult<WhitelistedResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    Ok(WhitelistedResponse {
+        whitelisted: WHITELIST.has(deps.storage, &addr),
+    })
+}
+
+fn query_receipt_pool(
+    deps: Deps,
+    env: Env,
+) -> StdResult<ReceiptPoolResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let pool = RECEIPT_POOL.load(deps.storage)?;
+    let pending = pending_receipt_reward(&pool, &env, config.apr);
+
+    let exchange_rate = if pool.total_supply.is_zero() {
+        Decimal::one()
+    } else {
+        Decimal::from_ratio(pool.total_underlying + pending, pool.total_supply)
+    };
+
+    Ok(ReceiptPoolResponse {
+        receipt_token: config.receipt_token.map(|t| t.to_string()),
+        total_underlying: pool.total_underlying,
+        total_supply: pool.total_supply,
+        exchange_rate,
+    })
+}
+
+fn query_vesting(
+    deps: Deps,
+    env: Env,
+    address: String,
+) -> StdResult<VestingResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+
+    let mut resp = VestingResponse {
+        total: Uint128::zero(),
+        released: Uint128::zero(),
+        vested: Uint128::zero(),
+        locked: Uint128::zero(),
+        start_time: 0,
+        cliff_end: 0,
+        end_time: 0,
+    };
+
+    let tranches = VESTING
+        .prefix(&addr)
+        .range(deps.storage, None, None, Order::Ascending);
+    for (i, item) in tranches.enumerate() {
+        let (_, schedule) = item?;
+        let vested = schedule.vested(env.block.time);
+        let start = schedule.start.seconds();
+
+        resp.total += schedule.total;
+        resp.released += schedule.released;
+        resp.vested += vested - schedule.released;
+        resp.locked += schedule.total - vested;
+        if i == 0 {
+            resp.start_time = start;
+            resp.cliff_end = start + schedule.cliff;
+            resp.end_time = start + schedule.duration;
+        } else {
+            resp.start_time = resp.start_time.min(start);
+            resp.cliff_end = resp.cliff_end.min(start + schedule.cliff);
+            resp.end_time = resp.end_time.max(start + schedule.duration);
+        }
+    }
+
+    Ok(resp)
+}
+
+fn query_unbonding(
+    deps: Deps,
+    address: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<UnbondingResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let unbonding = UNBONDING
+        .prefix(&addr)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (id, entry) = item?;
+            Ok(UnbondingEntry {
+                id,
+                amount: entry.amount,
+                release_at: entry.release_at.seconds(),
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(UnbondingResponse { unbonding })
+}
+
+fn query_slash_history(
+    deps: Deps,
+    address: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<SlashHistoryResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let slashes = SLASHES
+        .prefix(&addr)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (id, record) = item?;
+            Ok(SlashResponse {
+                id,
+                amount: record.amount,
+                fraction: record.fraction,
+                reason: record.reason,
+                height: record.height,
+                time: record.time.seconds(),
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(SlashHistoryResponse { slashes })
 }
\ No newline at end of file
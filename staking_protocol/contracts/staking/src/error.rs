@@ -1,27 +1,64 @@
-use cosmwasm_std::StdError;
-use thiserror::Error;
-use cw_ownable::OwnershipError;
-
-#[derive(Error, Debug)]
-pub enum ContractError {
-    #[error("{0}")]
-    Std(#[from] StdError),
-
-    #[error("{0}")]
-    Ownership(#[from] OwnershipError),
-
-    #[error("Unauthorized")]
-    Unauthorized {},
-
-    #[error("Lockup period not expired")]
-    LockupNotExpired {},
-
-    #[error("Insufficient stake")]
-    InsufficientStake {},
-
-    #[error("Zero reward")]
-    ZeroReward {},
-
-    #[error("No stake found")]
-    NoStake {},
+use cosmwasm_std::{StdError, Uint128};
+use thiserror::Error;
+use cw_ownable::OwnershipError;
+use cw_utils::ParseReplyError;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Ownership(#[from] OwnershipError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Lockup period not expired")]
+    LockupNotExpired {},
+
+    #[error("Insufficient stake")]
+    InsufficientStake {},
+
+    #[error("Zero reward")]
+    ZeroReward {},
+
+    #[error("No stake found")]
+    NoStake {},
+
+    #[error("Stake exceeds the pool limit of {max}")]
+    MaxTotalStakeExceeded { max: Uint128 },
+
+    #[error("Stake exceeds the per-address limit of {max}")]
+    MaxStakePerAddressExceeded { max: Uint128 },
+
+    #[error("Address is not whitelisted")]
+    NotWhitelisted {},
+
+    #[error("{0}")]
+    ParseReply(#[from] ParseReplyError),
+
+    #[error("Unknown reply id: {id}")]
+    UnknownReplyId { id: u64 },
+
+    #[error("Receipt token is not set")]
+    ReceiptTokenNotSet {},
+
+    #[error("Amount is too small to mint or redeem receipts")]
+    ZeroReceiptAmount {},
+
+    #[error("Receipts in circulation have no stake left behind them")]
+    InsolventReceiptPool {},
+
+    #[error("Vesting cliff must not exceed a non-zero duration")]
+    InvalidVesting {},
+
+    #[error("Nothing to withdraw")]
+    NothingToWithdraw {},
+
+    #[error("Slash fraction must be greater than 0 and at most 1")]
+    InvalidSlashFraction {},
+
+    #[error("Slash amount rounds down to zero")]
+    ZeroSlashAmount {},
 }
\ No newline at end of file
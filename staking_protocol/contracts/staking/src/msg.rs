@@ -1,70 +1,221 @@
-use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Uint128; 
-use cw_ownable::Action;
-
-#[cw_serde]
-pub struct InstantiateMsg {
-    pub owner: String,
-    pub token_address: String,
-    pub reward_rate: u64,
-    pub lockup_period: u64,
-}
-
-#[cw_serde]
-pub enum ExecuteMsg {
-    Stake {
-        amount: Uint128,
-    },
-    Unstake {
-        amount: Uint128
-    },
-    ClaimRewards {},
-    ChangeConfig {
-        new_apr: u64,
-        new_lockup_period: u64,
-    },
-    UpdateOwnership(Action),
-}
-
-#[derive(QueryResponses)]
-#[cw_serde]
-pub enum QueryMsg {
-    #[returns(StakeResponse)]
-    Stake {address: String},
-
-    #[returns(ConfigResponse)]
-    Config {},
-
-    #[returns(RewardResponse)]
-    Reward {address: String},
-
-    #[returns(TotalStakedResponse)]
-    TotalStaked {},
-
-    #[returns(cw_ownable::Ownership<cosmwasm_std::Addr>)]
-    Ownership {},
-}
-
-#[cw_serde]
-pub struct StakeResponse {
-    pub amount: Uint128,
-    pub stake_time: u64,
-}
-
-#[cw_serde]
-pub struct ConfigResponse {
-    pub token_address: String,
-    pub apr: u64,
-    pub lockup_period: u64,
-}
-
-#[cw_serde]
-pub struct RewardResponse {
-    pub amount: Uint128,
-}
-
-#[cw_serde]
-pub struct TotalStakedResponse {
-    pub total: Uint128,
-}
-
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Decimal, Uint128};
+use cw_ownable::Action;
+
+use crate::state::RewardVesting;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub owner: String,
+    pub token_address: String,
+    pub reward_rate: u64,
+    pub lockup_period: u64,
+    pub max_total_stake: Option<Uint128>,
+    pub max_stake_per_address: Option<Uint128>,
+    /// Starts the pool in the whitelist phase with these addresses allowed to stake
+    pub whitelist: Option<Vec<String>>,
+    pub receipt_token: Option<ReceiptTokenMsg>,
+    pub reward_vesting: Option<RewardVesting>,
+    pub slasher: Option<String>,
+    pub slash_recipient: Option<String>,
+}
+
+#[cw_serde]
+pub enum ReceiptTokenMsg {
+    /// Already deployed cw20 whose minter is (or will be) this contract.
+    /// Slashing receipt holders needs it to be a compliance mode cw20-token
+    /// owned by this contract
+    Existing { address: String },
+    /// Instantiates a fresh compliance mode cw20-token with this contract as
+    /// minter and owner
+    New {
+        code_id: u64,
+        name: String,
+        symbol: String,
+        decimals: u8,
+    },
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    Stake {
+        amount: Uint128,
+    },
+    Unstake {
+        amount: Uint128
+    },
+    ClaimRewards {},
+    /// Stakes `amount` and mints receipts at the current exchange rate
+    LiquidStake {
+        amount: Uint128,
+    },
+    /// Redeems `amount` receipts for the underlying stake, the receipts
+    /// must be approved to this contract beforehand. The underlying unbonds
+    /// for the lockup period before it can be withdrawn
+    LiquidUnstake {
+        amount: Uint128,
+    },
+    /// Pays out the sender's unbonded liquid stake
+    WithdrawUnbonded {},
+    ChangeConfig {
+        new_apr: u64,
+        new_lockup_period: u64,
+    },
+    UpdateStakeLimits {
+        max_total_stake: Option<Uint128>,
+        max_stake_per_address: Option<Uint128>,
+    },
+    UpdateRewardVesting {
+        reward_vesting: Option<RewardVesting>,
+    },
+    /// Releases the vested part of the sender's claimed rewards
+    WithdrawVested {},
+    /// Owner or slasher only, slashes `fraction` of the address' stake,
+    /// the receipts it holds and its unbonding stake
+    Slash {
+        address: String,
+        fraction: Decimal,
+        reason: String,
+    },
+    UpdateSlashing {
+        slasher: Option<String>,
+        slash_recipient: Option<String>,
+    },
+    UpdateWhitelist {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    SetWhitelistPhase {
+        enabled: bool,
+    },
+    UpdateOwnership(Action),
+}
+
+#[derive(QueryResponses)]
+#[cw_serde]
+pub enum QueryMsg {
+    #[returns(StakeResponse)]
+    Stake {address: String},
+
+    #[returns(ConfigResponse)]
+    Config {},
+
+    #[returns(RewardResponse)]
+    Reward {address: String},
+
+    #[returns(TotalStakedResponse)]
+    TotalStaked {},
+
+    #[returns(WhitelistedResponse)]
+    Whitelisted {address: String},
+
+    #[returns(ReceiptPoolResponse)]
+    ReceiptPool {},
+
+    #[returns(VestingResponse)]
+    Vesting {address: String},
+
+    #[returns(UnbondingResponse)]
+    Unbonding {
+        address: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    #[returns(SlashHistoryResponse)]
+    SlashHistory {
+        address: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    #[returns(cw_ownable::Ownership<cosmwasm_std::Addr>)]
+    Ownership {},
+}
+
+#[cw_serde]
+pub struct StakeResponse {
+    pub amount: Uint128,
+    pub stake_time: u64,
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub token_address: String,
+    pub apr: u64,
+    pub lockup_period: u64,
+    pub max_total_stake: Option<Uint128>,
+    pub max_stake_per_address: Option<Uint128>,
+    pub whitelist_enabled: bool,
+    pub receipt_token: Option<String>,
+    pub reward_vesting: Option<RewardVesting>,
+    pub slasher: Option<String>,
+    pub slash_recipient: Option<String>,
+}
+
+#[cw_serde]
+pub struct RewardResponse {
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct TotalStakedResponse {
+    pub total: Uint128,
+}
+
+#[cw_serde]
+pub struct WhitelistedResponse {
+    pub whitelisted: bool,
+}
+
+#[cw_serde]
+pub struct ReceiptPoolResponse {
+    pub receipt_token: Option<String>,
+    pub total_underlying: Uint128,
+    pub total_supply: Uint128,
+    /// Underlying stake tokens per receipt, including rewards not compounded yet
+    pub exchange_rate: Decimal,
+}
+
+/// Summed over all vesting tranches of the address
+#[cw_serde]
+pub struct VestingResponse {
+    pub total: Uint128,
+    pub released: Uint128,
+    /// Vested and not yet withdrawn
+    pub vested: Uint128,
+    pub locked: Uint128,
+    /// Start of the oldest tranche
+    pub start_time: u64,
+    /// Earliest cliff end among the tranches
+    pub cliff_end: u64,
+    /// End of the last tranche to fully vest
+    pub end_time: u64,
+}
+
+#[cw_serde]
+pub struct UnbondingEntry {
+    pub id: u64,
+    pub amount: Uint128,
+    pub release_at: u64,
+}
+
+#[cw_serde]
+pub struct UnbondingResponse {
+    pub unbonding: Vec<UnbondingEntry>,
+}
+
+#[cw_serde]
+pub struct SlashResponse {
+    pub id: u64,
+    pub amount: Uint128,
+    pub fraction: Decimal,
+    pub reason: String,
+    pub height: u64,
+    pub time: u64,
+}
+
+#[cw_serde]
+pub struct SlashHistoryResponse {
+    pub slashes: Vec<SlashResponse>,
+}
@@ -1,20 +1,94 @@
-use cosmwasm_std::{Addr, Uint128, Timestamp};
-use cw_storage_plus::{Item, Map};
-use cosmwasm_schema::cw_serde;
-
-#[cw_serde]
-pub struct Config {
-    pub token_address: Addr,
-    pub apr: u64, // 1% = 100, 10% = 1000
-    pub lockup_period: u64,
-}
-
-#[cw_serde]
-pub struct StakeInfo {
-    pub amount: Uint128,
-    pub stake_time: Timestamp,
-}
-
-pub const CONFIG: Item<Config> = Item::new("config");
-pub const STAKES: Map<&Addr, StakeInfo> = Map::new("stakes");
-pub const TOTAL_STAKED: Item<Uint128> = Item::new("total_staked");
\ No newline at end of file
+use cosmwasm_std::{Addr, Decimal, Empty, Uint128, Timestamp};
+use cw_storage_plus::{Item, Map};
+use cosmwasm_schema::cw_serde;
+
+#[cw_serde]
+pub struct Config {
+    pub token_address: Addr,
+    pub apr: u64, // 1% = 100, 10% = 1000
+    pub lockup_period: u64,
+    pub max_total_stake: Option<Uint128>,
+    pub max_stake_per_address: Option<Uint128>,
+    // launch phase: only addresses from WHITELIST may stake
+    pub whitelist_enabled: bool,
+    // cw20 receipt minted for liquid stakes, this contract is its minter
+    pub receipt_token: Option<Addr>,
+    // when set, claimed rewards vest instead of being paid out at once
+    pub reward_vesting: Option<RewardVesting>,
+    // may slash stakes next to the owner
+    pub slasher: Option<Addr>,
+    // receives slashed stake, burned when not set
+    pub slash_recipient: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct RewardVesting {
+    pub cliff: u64,    // seconds before anything vests
+    pub duration: u64, // seconds until everything vests, cliff included
+}
+
+#[cw_serde]
+pub struct StakeInfo {
+    pub amount: Uint128,
+    pub stake_time: Timestamp,
+}
+
+#[cw_serde]
+pub struct ReceiptPool {
+    // stake tokens backing the receipts, including compounded rewards
+    pub total_underlying: Uint128,
+    // receipts in circulation
+    pub total_supply: Uint128,
+    pub last_compound: Timestamp,
+}
+
+#[cw_serde]
+pub struct VestingSchedule {
+    pub total: Uint128,
+    pub released: Uint128,
+    pub start: Timestamp,
+    pub cliff: u64,
+    pub duration: u64,
+}
+
+impl VestingSchedule {
+    pub fn vested(&self, now: Timestamp) -> Uint128 {
+        let elapsed = now.seconds().saturating_sub(self.start.seconds());
+        if elapsed < self.cliff {
+            Uint128::zero()
+        } else if elapsed >= self.duration {
+            self.total
+        } else {
+            self.total.multiply_ratio(elapsed, self.duration)
+        }
+    }
+}
+
+#[cw_serde]
+pub struct Unbonding {
+    pub amount: Uint128,
+    pub release_at: Timestamp,
+}
+
+#[cw_serde]
+pub struct SlashRecord {
+    pub amount: Uint128,
+    pub fraction: Decimal,
+    pub reason: String,
+    pub height: u64,
+    pub time: Timestamp,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const STAKES: Map<&Addr, StakeInfo> = Map::new("stakes");
+pub const TOTAL_STAKED: Item<Uint128> = Item::new("total_staked");
+pub const WHITELIST: Map<&Addr, Empty> = Map::new("whitelist");
+pub const RECEIPT_POOL: Item<ReceiptPool> = Item::new("receipt_pool");
+// redeemed receipts waiting out the lockup period
+pub const UNBONDING: Map<(&Addr, u64), Unbonding> = Map::new("unbonding");
+pub const UNBONDING_COUNT: Map<&Addr, u64> = Map::new("unbonding_count");
+// one tranche per claim, so earlier rewards keep their own start
+pub const VESTING: Map<(&Addr, u64), VestingSchedule> = Map::new("vesting");
+pub const VESTING_COUNT: Map<&Addr, u64> = Map::new("vesting_count");
+pub const SLASHES: Map<(&Addr, u64), SlashRecord> = Map::new("slashes");
+pub const SLASH_COUNT: Map<&Addr, u64> = Map::new("slash_count");
\ No newline at end of file
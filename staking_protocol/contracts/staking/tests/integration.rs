@@ -1,406 +1,1166 @@
-use cosmwasm_std::{Addr, Empty, Uint128};
-use cw_multi_test::{App, ContractWrapper, Executor};
-
-use staking::msg::{ConfigResponse, InstantiateMsg, QueryMsg, TotalStakedResponse, StakeResponse};
-use cw20_token::msg::BalanceResponse;
-
-use crate::test_utils::TestSetup;
-
-fn cw20_contract() -> Box<dyn cw_multi_test::Contract<Empty>> {
-    let contract = ContractWrapper::new(
-        cw20_token::contract::execute,
-        cw20_token::contract::instantiate,
-        cw20_token::contract::query,
-    );
-    Box::new(contract)
-}
-
-fn staking_contract() -> Box<dyn cw_multi_test::Contract<Empty>> {
-    let contract = ContractWrapper::new(
-        staking::contract::execute,
-        staking::contract::instantiate,
-        staking::contract::query,
-    );
-    Box::new(contract)
-}
-
-mod test_utils {
-
-    use super::*;
-
-    pub struct TestSetup {
-        pub app: App,
-        pub staking_addr: Addr,
-        pub token_addr: Addr,
-        pub owner: String,
-        pub user1: String,
-        pub apr: u64,
-        pub period: u64,
-    }
-
-    impl TestSetup {
-        pub fn new() -> Self {
-            let mut app = App::default();
-            // let owner = Addr::unchecked("owner");
-            // let user1 = Addr::unchecked("user1");
-            // let user2 = Addr::unchecked("user2");
-
-            let owner = "owner".to_string();
-            let user1 = "user1".to_string();
-
-            let apr = 1000;
-            let period = 60 * 60 * 24 * 30;
-
-            let cw20_code_id = app.store_code(cw20_contract());
-            let token_addr = app
-                .instantiate_contract(
-                    cw20_code_id,
-                    Addr::unchecked("owner"),
-                    &cw20_token::msg::InstantiateMsg {
-                        name: "Test Token".to_string(),
-                        symbol: "TST".to_string(),
-                        decimals: 18,
-                        minter: Some("owner".to_string()),
-                    },
-                    &[],
-                    "Test Token",
-                    None,
-                )
-                .unwrap();
-
-            let staking_code_id = app.store_code(staking_contract());
-            let staking_addr = app
-                .instantiate_contract(
-                    staking_code_id,
-                    Addr::unchecked("owner"),
-                    &InstantiateMsg {
-                        owner: "owner".to_string(),
-                        token_address: token_addr.to_string(),
-                        reward_rate: apr,
-                        lockup_period: period,
-                    },
-                    &[],
-                    "Staking contract",
-                    None,
-                )
-                .unwrap();
-
-            TestSetup {
-                app,
-                staking_addr,
-                token_addr,
-                owner,
-                user1,
-                apr,
-                period,
-            }
-        }
-
-        pub fn mint_tokens(&mut self, recipient: &str, amount: Uint128) {
-            self.app
-                .execute_contract(
-                    Addr::unchecked(&self.owner),
-                    self.token_addr.clone(),
-                    &cw20_token::msg::ExecuteMsg::Mint {
-                        recipient: recipient.to_string(),
-                        amount: amount,
-                    },
-                    &[],
-                )
-                .unwrap();
-        }
-
-        pub fn approve_tokens(&mut self, owner: &str, spender: &Addr, amount: Uint128) {
-            self.app
-                .execute_contract(
-                    Addr::unchecked(owner),
-                    self.token_addr.clone(),
-                    &cw20_token::msg::ExecuteMsg::Approve {
-                        spender: spender.to_string(),
-                        amount: amount,
-                    },
-                    &[],
-                )
-                .unwrap();
-        }
-
-        pub fn stake(&mut self, user: &str, amount: Uint128) {
-            self.app
-                .execute_contract(
-                    Addr::unchecked(user),
-                    self.staking_addr.clone(),
-                    &staking::msg::ExecuteMsg::Stake { amount: amount },
-                    &[],
-                )
-                .unwrap();
-        }
-
-        pub fn change_config(&mut self, user: &str, apr: u64, lockup_period: u64) {
-            self.app
-                .execute_contract(
-                    Addr::unchecked(user),
-                    self.staking_addr.clone(),
-                    &staking::msg::ExecuteMsg::ChangeConfig {
-                        new_apr: apr,
-                        new_lockup_period: lockup_period,
-                    },
-                    &[],
-                )
-                .unwrap();
-        }
-
-        pub fn unstake(&mut self, user: &str, amount: Uint128) {
-            self.app
-                .execute_contract(
-                    Addr::unchecked(user),
-                    self.staking_addr.clone(),
-                    &staking::msg::ExecuteMsg::Unstake {
-                        amount: amount,
-                    },
-                    &[],
-                )
-                .unwrap();
-        }
-
-        pub fn claim_rewards(&mut self, user: &str) {
-            self.app
-                .execute_contract(
-                    Addr::unchecked(user),
-                    self.staking_addr.clone(),
-                    &staking::msg::ExecuteMsg::ClaimRewards {},
-                    &[],
-                ).unwrap();
-        }
-
-        pub fn set_staking_contract_minter(&mut self) {
-            self.app
-                .execute_contract(
-                    self.get_owner_addr(),
-                    self.token_addr.clone(),
-                    &cw20_token::msg::ExecuteMsg::UpdateMinter {
-                        new_minter: Some(self.staking_addr.to_string()),
-                    },
-                    &[],
-                ).unwrap();
-        }
-
-        pub fn advance_time(&mut self, seconds: u64) {
-            self.app.update_block(|block| {
-                block.time = block.time.plus_seconds(seconds);
-                block.height+=1;
-            });
-        }
-
-        // pub fn staking_addr_str(&self) -> String {
-        //     self.staking_addr.to_string()
-        // }
-
-        // pub fn token_addr_str(&self) -> String {
-        //     self.token_addr.to_string()
-        // }
-
-        pub fn get_owner_addr(&self) -> Addr {
-            Addr::unchecked(&self.owner)
-        }
-
-        // pub fn get_user1_addr(&self) -> Addr {
-        //     Addr::unchecked(&self.user1)
-        // }
-
-        // pub fn get_user2_addr(&self) -> Addr {
-        //     Addr::unchecked(&self.user2)
-        // }
-    }
-}
-
-#[test]
-
-fn test_proper_initialization() {
-    let setup = TestSetup::new();
-
-    let config: ConfigResponse = setup.app
-        .wrap()
-        .query_wasm_smart(&setup.staking_addr, &QueryMsg::Config {})
-        .unwrap();
-
-    assert_eq!(config.apr, setup.apr);
-    assert_eq!(config.lockup_period, setup.period);
-    assert_eq!(config.token_address, setup.token_addr.to_string());
-
-    let total_staked: TotalStakedResponse = setup.app
-        .wrap()
-        .query_wasm_smart(&setup.staking_addr, &QueryMsg::TotalStaked {})
-        .unwrap();
-
-    assert_eq!(total_staked.total, Uint128::zero());
-}
-
-#[test]
-fn test_successful_stake() {
-    let mut setup = TestSetup::new();
-    let staked_amount = Uint128::from(100000u128);
-    let staking_addr = setup.staking_addr.clone();
-
-    setup.mint_tokens("user1", staked_amount);
-    setup.approve_tokens("user1", &staking_addr, staked_amount);
-    setup.stake("user1", staked_amount);
-
-    let staked_info: StakeResponse = setup.app
-        .wrap()
-        .query_wasm_smart(&setup.staking_addr, &QueryMsg::Stake {
-            address: setup.user1.clone(),
-        })
-        .unwrap();
-
-    let total_staked_after: TotalStakedResponse = setup.app
-        .wrap()
-        .query_wasm_smart(&setup.staking_addr, &QueryMsg::TotalStaked {})
-        .unwrap();
-
-    let user1_balance_after: BalanceResponse = setup.app
-        .wrap()
-        .query_wasm_smart(&setup.token_addr, &cw20_token::msg::QueryMsg::Balance {
-            address: setup.user1.clone(),
-        })
-        .unwrap();
-
-    let staking_balance_after: BalanceResponse = setup.app
-        .wrap()
-        .query_wasm_smart(&setup.token_addr, &cw20_token::msg::QueryMsg::Balance {
-            address: setup.staking_addr.to_string(),
-        })
-        .unwrap();
-
-    assert_eq!(staked_info.amount, staked_amount);
-    assert_eq!(staked_amount, total_staked_after.total);
-    assert_eq!(user1_balance_after.balance, Uint128::zero());
-    assert_eq!(staking_balance_after.balance, staked_amount);
-}
-
-
-#[test]
-pub fn test_successful_unstake_after_lockup() {
-    let mut setup = TestSetup::new();
-    let staked_amount = Uint128::from(100000u128);
-    let staking_addr = setup.staking_addr.clone();
-
-    setup.mint_tokens("user1", staked_amount);
-    setup.approve_tokens("user1", &staking_addr, staked_amount);
-    setup.stake("user1", staked_amount);
-    setup.set_staking_contract_minter();
-
-    let config: ConfigResponse = setup.app
-        .wrap()
-        .query_wasm_smart(&setup.staking_addr, &QueryMsg::Config {})
-        .unwrap();
-
-    setup.advance_time(config.lockup_period);
-
-    setup.unstake("user1", staked_amount);
-
-    let user_balance_after_unstake: BalanceResponse = setup.app
-        .wrap()
-        .query_wasm_smart(&setup.token_addr, &cw20_token::msg::QueryMsg::Balance {
-            address: setup.user1.clone(),
-        })
-        .unwrap();
-
-    let staking_balance_after_unstake: BalanceResponse = setup.app
-        .wrap()
-        .query_wasm_smart(&setup.token_addr, &cw20_token::msg::QueryMsg::Balance {
-            address: setup.staking_addr.to_string(),
-        })
-        .unwrap();
-
-    let total_staked_after: TotalStakedResponse = setup.app
-        .wrap()
-        .query_wasm_smart(&setup.staking_addr, &QueryMsg::TotalStaked {})
-        .unwrap();
-
-    const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
-
-    let stake = staked_amount.u128();
-    let rate = config.apr as u128;
-    let time = config.lockup_period as u128;
-    let expected_reward = stake
-        .checked_mul(rate).unwrap()
-        .checked_mul(time).unwrap()
-        .checked_div(10_000).unwrap()
-        .checked_div(SECONDS_PER_YEAR as u128).unwrap();
-
-    assert_eq!(user_balance_after_unstake.balance, Uint128::from(expected_reward + stake));
-    assert_eq!(staking_balance_after_unstake.balance, Uint128::zero());
-    assert_eq!(total_staked_after.total, Uint128::zero());
-}
-
-#[test]
-pub fn test_successful_clain_rewards() {
-    let mut setup = TestSetup::new();
-    let staked_amount = Uint128::from(100000u128);
-    let staking_addr = setup.staking_addr.clone();
-
-    setup.mint_tokens("user1", staked_amount);
-    setup.approve_tokens("user1", &staking_addr, staked_amount);
-    setup.stake("user1", staked_amount);
-    setup.set_staking_contract_minter();
-
-    let config: ConfigResponse = setup.app
-        .wrap()
-        .query_wasm_smart(&setup.staking_addr, &QueryMsg::Config {})
-        .unwrap();
-
-    setup.advance_time(config.lockup_period);
-    setup.claim_rewards("user1");
-    let time_after_claiming = setup.app.block_info().time.seconds();
-
-    let total_staked_after: TotalStakedResponse = setup.app
-        .wrap()
-        .query_wasm_smart(&setup.staking_addr, &QueryMsg::TotalStaked {})
-        .unwrap();
-
-    let user_balance_after_claim_rewards: BalanceResponse = setup.app
-        .wrap()
-        .query_wasm_smart(&setup.token_addr, &cw20_token::msg::QueryMsg::Balance {
-            address: setup.user1.clone(),
-        })
-        .unwrap();
-
-    let staked_info: StakeResponse = setup.app
-        .wrap()
-        .query_wasm_smart(&setup.staking_addr, &QueryMsg::Stake {
-            address: setup.user1.clone(),
-        })
-        .unwrap();
-
-    const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
-    let stake = staked_amount.u128();
-    let rate = config.apr as u128;
-    let time = config.lockup_period as u128;
-    let expected_reward = stake
-        .checked_mul(rate).unwrap()
-        .checked_mul(time).unwrap()
-        .checked_div(10_000).unwrap()
-        .checked_div(SECONDS_PER_YEAR as u128).unwrap();
-
-    assert_eq!(total_staked_after.total, staked_amount);
-    assert_eq!(user_balance_after_claim_rewards.balance, Uint128::from(expected_reward));
-    assert_eq!(staked_info.amount, staked_amount);
-    assert_eq!(staked_info.stake_time, time_after_claiming);
-}
-
-#[test]
-pub fn test_successful_change_config() {
-    let mut setup = TestSetup::new();
-    let new_apr = setup.apr*2;
-    let new_lockup_period = setup.period*2;
-
-    setup.change_config(&setup.owner.clone(), new_apr, new_lockup_period);
-
-    let config: ConfigResponse = setup.app
-        .wrap()
-        .query_wasm_smart(&setup.staking_addr, &QueryMsg::Config {})
-        .unwrap();
-
-    assert_eq!(config.apr, new_apr);
-    assert_eq!(config.lockup_period, new_lockup_period);
-}
+use cosmwasm_std::{Addr, Empty, Order, Uint128};
+use cw_multi_test::{App, ContractWrapper, Executor};
+
+use staking::ContractError;
+use staking::msg::{
+    ConfigResponse, InstantiateMsg, QueryMsg, ReceiptPoolResponse, ReceiptTokenMsg,
+    SlashHistoryResponse, TotalStakedResponse, StakeResponse, UnbondingResponse, VestingResponse,
+    WhitelistedResponse,
+};
+use staking::state::{ReceiptPool, RewardVesting, RECEIPT_POOL, VESTING};
+use cw20_token::msg::BalanceResponse;
+
+use crate::test_utils::TestSetup;
+
+fn cw20_contract() -> Box<dyn cw_multi_test::Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw20_token::contract::execute,
+        cw20_token::contract::instantiate,
+        cw20_token::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn staking_contract() -> Box<dyn cw_multi_test::Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        staking::contract::execute,
+        staking::contract::instantiate,
+        staking::contract::query,
+    )
+    .with_reply(staking::contract::reply);
+    Box::new(contract)
+}
+
+mod test_utils {
+
+    use super::*;
+
+    pub struct TestSetup {
+        pub app: App,
+        pub staking_addr: Addr,
+        pub token_addr: Addr,
+        pub receipt_addr: Option<Addr>,
+        pub owner: String,
+        pub user1: String,
+        pub apr: u64,
+        pub period: u64,
+    }
+
+    impl TestSetup {
+        pub fn new() -> Self {
+            Self::build(false)
+        }
+
+        pub fn new_with_receipt_token() -> Self {
+            Self::build(true)
+        }
+
+        fn build(with_receipt_token: bool) -> Self {
+            let mut app = App::default();
+            // let owner = Addr::unchecked("owner");
+            // let user1 = Addr::unchecked("user1");
+            // let user2 = Addr::unchecked("user2");
+
+            let owner = app.api().addr_make("owner").to_string();
+            let user1 = app.api().addr_make("user1").to_string();
+
+            let apr = 1000;
+            let period = 60 * 60 * 24 * 30;
+
+            let cw20_code_id = app.store_code(cw20_contract());
+            let token_addr = app
+                .instantiate_contract(
+                    cw20_code_id,
+                    Addr::unchecked(&owner),
+                    &cw20_token::msg::InstantiateMsg {
+                        name: "Test Token".to_string(),
+                        symbol: "TST".to_string(),
+                        decimals: 18,
+                        initial_balances: vec![],
+                        owner: None,
+                        minter: Some(owner.clone()),
+                        cap: None,
+                        marketing: None,
+                        compliance: false,
+                        transfer_fee_bps: None,
+                        fee_recipient: None,
+                    },
+                    &[],
+                    "Test Token",
+                    None,
+                )
+                .unwrap();
+
+            let receipt_token = with_receipt_token.then(|| ReceiptTokenMsg::New {
+                code_id: cw20_code_id,
+                name: "Staked Test Token".to_string(),
+                symbol: "STST".to_string(),
+                decimals: 18,
+            });
+
+            let staking_code_id = app.store_code(staking_contract());
+            let staking_addr = app
+                .instantiate_contract(
+                    staking_code_id,
+                    Addr::unchecked(&owner),
+                    &InstantiateMsg {
+                        owner: owner.clone(),
+                        token_address: token_addr.to_string(),
+                        reward_rate: apr,
+                        lockup_period: period,
+                        max_total_stake: None,
+                        max_stake_per_address: None,
+                        whitelist: None,
+                        receipt_token,
+                        reward_vesting: None,
+                        slasher: None,
+                        slash_recipient: None,
+                    },
+                    &[],
+                    "Staking contract",
+                    None,
+                )
+                .unwrap();
+
+            let config: ConfigResponse = app
+                .wrap()
+                .query_wasm_smart(&staking_addr, &QueryMsg::Config {})
+                .unwrap();
+            let receipt_addr = config.receipt_token.map(Addr::unchecked);
+
+            TestSetup {
+                app,
+                staking_addr,
+                token_addr,
+                receipt_addr,
+                owner,
+                user1,
+                apr,
+                period,
+            }
+        }
+
+        pub fn mint_tokens(&mut self, recipient: &str, amount: Uint128) {
+            self.app
+                .execute_contract(
+                    Addr::unchecked(&self.owner),
+                    self.token_addr.clone(),
+                    &cw20_token::msg::ExecuteMsg::Mint {
+                        recipient: self.addr(recipient).to_string(),
+                        amount,
+                    },
+                    &[],
+                )
+                .unwrap();
+        }
+
+        pub fn approve_tokens(&mut self, owner: &str, spender: &Addr, amount: Uint128) {
+            self.app
+                .execute_contract(
+                    self.addr(owner),
+                    self.token_addr.clone(),
+                    &cw20_token::msg::ExecuteMsg::Approve {
+                        spender: spender.to_string(),
+                        amount,
+                    },
+                    &[],
+                )
+                .unwrap();
+        }
+
+        pub fn stake(&mut self, user: &str, amount: Uint128) {
+            self.app
+                .execute_contract(
+                    self.addr(user),
+                    self.staking_addr.clone(),
+                    &staking::msg::ExecuteMsg::Stake { amount },
+                    &[],
+                )
+                .unwrap();
+        }
+
+        pub fn stake_err(&mut self, user: &str, amount: Uint128) -> ContractError {
+            self.app
+                .execute_contract(
+                    self.addr(user),
+                    self.staking_addr.clone(),
+                    &staking::msg::ExecuteMsg::Stake { amount },
+                    &[],
+                )
+                .unwrap_err()
+                .downcast()
+                .unwrap()
+        }
+
+        pub fn liquid_stake(&mut self, user: &str, amount: Uint128) {
+            self.app
+                .execute_contract(
+                    self.addr(user),
+                    self.staking_addr.clone(),
+                    &staking::msg::ExecuteMsg::LiquidStake { amount },
+                    &[],
+                )
+                .unwrap();
+        }
+
+        pub fn liquid_unstake(&mut self, user: &str, amount: Uint128) {
+            self.app
+                .execute_contract(
+                    self.addr(user),
+                    self.staking_addr.clone(),
+                    &staking::msg::ExecuteMsg::LiquidUnstake { amount },
+                    &[],
+                )
+                .unwrap();
+        }
+
+        pub fn liquid_stake_err(&mut self, user: &str, amount: Uint128) -> ContractError {
+            self.app
+                .execute_contract(
+                    self.addr(user),
+                    self.staking_addr.clone(),
+                    &staking::msg::ExecuteMsg::LiquidStake { amount },
+                    &[],
+                )
+                .unwrap_err()
+                .downcast()
+                .unwrap()
+        }
+
+        pub fn approve_receipts(&mut self, owner: &str, amount: Uint128) {
+            self.app
+                .execute_contract(
+                    self.addr(owner),
+                    self.receipt_addr.clone().unwrap(),
+                    &cw20_token::msg::ExecuteMsg::Approve {
+                        spender: self.staking_addr.to_string(),
+                        amount,
+                    },
+                    &[],
+                )
+                .unwrap();
+        }
+
+        pub fn withdraw_unbonded(&mut self, user: &str) -> Result<(), ContractError> {
+            self.app
+                .execute_contract(
+                    self.addr(user),
+                    self.staking_addr.clone(),
+                    &staking::msg::ExecuteMsg::WithdrawUnbonded {},
+                    &[],
+                )
+                .map(|_| ())
+                .map_err(|err| err.downcast().unwrap())
+        }
+
+        pub fn balance(&self, token: &Addr, address: &str) -> Uint128 {
+            let res: BalanceResponse = self.app
+                .wrap()
+                .query_wasm_smart(token, &cw20_token::msg::QueryMsg::Balance {
+                    address: self.addr(address).to_string(),
+                })
+                .unwrap();
+            res.balance
+        }
+
+        pub fn change_config(&mut self, user: &str, apr: u64, lockup_period: u64) {
+            self.app
+                .execute_contract(
+                    self.addr(user),
+                    self.staking_addr.clone(),
+                    &staking::msg::ExecuteMsg::ChangeConfig {
+                        new_apr: apr,
+                        new_lockup_period: lockup_period,
+                    },
+                    &[],
+                )
+                .unwrap();
+        }
+
+        pub fn unstake(&mut self, user: &str, amount: Uint128) {
+            self.app
+                .execute_contract(
+                    self.addr(user),
+                    self.staking_addr.clone(),
+                    &staking::msg::ExecuteMsg::Unstake {
+                        amount,
+                    },
+                    &[],
+                )
+                .unwrap();
+        }
+
+        pub fn claim_rewards(&mut self, user: &str) {
+            self.app
+                .execute_contract(
+                    self.addr(user),
+                    self.staking_addr.clone(),
+                    &staking::msg::ExecuteMsg::ClaimRewards {},
+                    &[],
+                ).unwrap();
+        }
+
+        pub fn set_staking_contract_minter(&mut self) {
+            self.app
+                .execute_contract(
+                    self.get_owner_addr(),
+                    self.token_addr.clone(),
+                    &cw20_token::msg::ExecuteMsg::UpdateMinter {
+                        new_minter: Some(self.staking_addr.to_string()),
+                    },
+                    &[],
+                ).unwrap();
+        }
+
+        pub fn update_stake_limits(&mut self, max_total_stake: Option<Uint128>, max_stake_per_address: Option<Uint128>) {
+            self.app
+                .execute_contract(
+                    self.get_owner_addr(),
+                    self.staking_addr.clone(),
+                    &staking::msg::ExecuteMsg::UpdateStakeLimits {
+                        max_total_stake,
+                        max_stake_per_address,
+                    },
+                    &[],
+                ).unwrap();
+        }
+
+        pub fn update_reward_vesting(&mut self, reward_vesting: Option<RewardVesting>) {
+            self.app
+                .execute_contract(
+                    self.get_owner_addr(),
+                    self.staking_addr.clone(),
+                    &staking::msg::ExecuteMsg::UpdateRewardVesting { reward_vesting },
+                    &[],
+                ).unwrap();
+        }
+
+        pub fn withdraw_vested(&mut self, user: &str) {
+            self.app
+                .execute_contract(
+                    self.addr(user),
+                    self.staking_addr.clone(),
+                    &staking::msg::ExecuteMsg::WithdrawVested {},
+                    &[],
+                ).unwrap();
+        }
+
+        pub fn update_whitelist(&mut self, add: Vec<String>, remove: Vec<String>) {
+            self.app
+                .execute_contract(
+                    self.get_owner_addr(),
+                    self.staking_addr.clone(),
+                    &staking::msg::ExecuteMsg::UpdateWhitelist { add, remove },
+                    &[],
+                ).unwrap();
+        }
+
+        pub fn set_whitelist_phase(&mut self, enabled: bool) {
+            self.app
+                .execute_contract(
+                    self.get_owner_addr(),
+                    self.staking_addr.clone(),
+                    &staking::msg::ExecuteMsg::SetWhitelistPhase { enabled },
+                    &[],
+                ).unwrap();
+        }
+
+        pub fn advance_time(&mut self, seconds: u64) {
+            self.app.update_block(|block| {
+                block.time = block.time.plus_seconds(seconds);
+                block.height+=1;
+            });
+        }
+
+        // pub fn staking_addr_str(&self) -> String {
+        //     self.staking_addr.to_string()
+        // }
+
+        // pub fn token_addr_str(&self) -> String {
+        //     self.token_addr.to_string()
+        // }
+
+        pub fn addr(&self, name: &str) -> Addr {
+            self.app.api().addr_make(name)
+        }
+
+        pub fn get_owner_addr(&self) -> Addr {
+            Addr::unchecked(&self.owner)
+        }
+
+        // pub fn get_user1_addr(&self) -> Addr {
+        //     Addr::unchecked(&self.user1)
+        // }
+
+        // pub fn get_user2_addr(&self) -> Addr {
+        //     Addr::unchecked(&self.user2)
+        // }
+    }
+}
+
+#[test]
+
+fn test_proper_initialization() {
+    let setup = TestSetup::new();
+
+    let config: ConfigResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.staking_addr, &QueryMsg::Config {})
+        .unwrap();
+
+    assert_eq!(config.apr, setup.apr);
+    assert_eq!(config.lockup_period, setup.period);
+    assert_eq!(config.token_address, setup.token_addr.to_string());
+
+    let total_staked: TotalStakedResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.staking_addr, &QueryMsg::TotalStaked {})
+        .unwrap();
+
+    assert_eq!(total_staked.total, Uint128::zero());
+}
+
+#[test]
+fn test_successful_stake() {
+    let mut setup = TestSetup::new();
+    let staked_amount = Uint128::from(100000u128);
+    let staking_addr = setup.staking_addr.clone();
+
+    setup.mint_tokens("user1", staked_amount);
+    setup.approve_tokens("user1", &staking_addr, staked_amount);
+    setup.stake("user1", staked_amount);
+
+    let staked_info: StakeResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.staking_addr, &QueryMsg::Stake {
+            address: setup.user1.clone(),
+        })
+        .unwrap();
+
+    let total_staked_after: TotalStakedResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.staking_addr, &QueryMsg::TotalStaked {})
+        .unwrap();
+
+    let user1_balance_after: BalanceResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.token_addr, &cw20_token::msg::QueryMsg::Balance {
+            address: setup.user1.clone(),
+        })
+        .unwrap();
+
+    let staking_balance_after: BalanceResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.token_addr, &cw20_token::msg::QueryMsg::Balance {
+            address: setup.staking_addr.to_string(),
+        })
+        .unwrap();
+
+    assert_eq!(staked_info.amount, staked_amount);
+    assert_eq!(staked_amount, total_staked_after.total);
+    assert_eq!(user1_balance_after.balance, Uint128::zero());
+    assert_eq!(staking_balance_after.balance, staked_amount);
+}
+
+
+#[test]
+pub fn test_successful_unstake_after_lockup() {
+    let mut setup = TestSetup::new();
+    let staked_amount = Uint128::from(100000u128);
+    let staking_addr = setup.staking_addr.clone();
+
+    setup.mint_tokens("user1", staked_amount);
+    setup.approve_tokens("user1", &staking_addr, staked_amount);
+    setup.stake("user1", staked_amount);
+    setup.set_staking_contract_minter();
+
+    let config: ConfigResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.staking_addr, &QueryMsg::Config {})
+        .unwrap();
+
+    setup.advance_time(config.lockup_period);
+
+    setup.unstake("user1", staked_amount);
+
+    let user_balance_after_unstake: BalanceResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.token_addr, &cw20_token::msg::QueryMsg::Balance {
+            address: setup.user1.clone(),
+        })
+        .unwrap();
+
+    let staking_balance_after_unstake: BalanceResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.token_addr, &cw20_token::msg::QueryMsg::Balance {
+            address: setup.staking_addr.to_string(),
+        })
+        .unwrap();
+
+    let total_staked_after: TotalStakedResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.staking_addr, &QueryMsg::TotalStaked {})
+        .unwrap();
+
+    const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+
+    let stake = staked_amount.u128();
+    let rate = config.apr as u128;
+    let time = config.lockup_period as u128;
+    let expected_reward = stake
+        .checked_mul(rate).unwrap()
+        .checked_mul(time).unwrap()
+        .checked_div(10_000).unwrap()
+        .checked_div(SECONDS_PER_YEAR as u128).unwrap();
+
+    assert_eq!(user_balance_after_unstake.balance, Uint128::from(expected_reward + stake));
+    assert_eq!(staking_balance_after_unstake.balance, Uint128::zero());
+    assert_eq!(total_staked_after.total, Uint128::zero());
+}
+
+#[test]
+pub fn test_successful_clain_rewards() {
+    let mut setup = TestSetup::new();
+    let staked_amount = Uint128::from(100000u128);
+    let staking_addr = setup.staking_addr.clone();
+
+    setup.mint_tokens("user1", staked_amount);
+    setup.approve_tokens("user1", &staking_addr, staked_amount);
+    setup.stake("user1", staked_amount);
+    setup.set_staking_contract_minter();
+
+    let config: ConfigResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.staking_addr, &QueryMsg::Config {})
+        .unwrap();
+
+    setup.advance_time(config.lockup_period);
+    setup.claim_rewards("user1");
+    let time_after_claiming = setup.app.block_info().time.seconds();
+
+    let total_staked_after: TotalStakedResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.staking_addr, &QueryMsg::TotalStaked {})
+        .unwrap();
+
+    let user_balance_after_claim_rewards: BalanceResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.token_addr, &cw20_token::msg::QueryMsg::Balance {
+            address: setup.user1.clone(),
+        })
+        .unwrap();
+
+    let staked_info: StakeResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.staking_addr, &QueryMsg::Stake {
+            address: setup.user1.clone(),
+        })
+        .unwrap();
+
+    const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+    let stake = staked_amount.u128();
+    let rate = config.apr as u128;
+    let time = config.lockup_period as u128;
+    let expected_reward = stake
+        .checked_mul(rate).unwrap()
+        .checked_mul(time).unwrap()
+        .checked_div(10_000).unwrap()
+        .checked_div(SECONDS_PER_YEAR as u128).unwrap();
+
+    assert_eq!(total_staked_after.total, staked_amount);
+    assert_eq!(user_balance_after_claim_rewards.balance, Uint128::from(expected_reward));
+    assert_eq!(staked_info.amount, staked_amount);
+    assert_eq!(staked_info.stake_time, time_after_claiming);
+}
+
+#[test]
+pub fn test_successful_change_config() {
+    let mut setup = TestSetup::new();
+    let new_apr = setup.apr*2;
+    let new_lockup_period = setup.period*2;
+
+    setup.change_config("owner", new_apr, new_lockup_period);
+
+    let config: ConfigResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.staking_addr, &QueryMsg::Config {})
+        .unwrap();
+
+    assert_eq!(config.apr, new_apr);
+    assert_eq!(config.lockup_period, new_lockup_period);
+}
+
+#[test]
+fn test_stake_limits() {
+    let mut setup = TestSetup::new();
+    let staking_addr = setup.staking_addr.clone();
+
+    setup.update_stake_limits(Some(Uint128::from(1500u128)), Some(Uint128::from(1000u128)));
+    setup.mint_tokens("user1", Uint128::from(2000u128));
+    setup.mint_tokens("user2", Uint128::from(2000u128));
+    setup.approve_tokens("user1", &staking_addr, Uint128::from(2000u128));
+    setup.approve_tokens("user2", &staking_addr, Uint128::from(2000u128));
+
+    setup.stake("user1", Uint128::from(600u128));
+    let err = setup.stake_err("user1", Uint128::from(401u128));
+    assert_eq!(err.to_string(), ContractError::MaxStakePerAddressExceeded { max: Uint128::from(1000u128) }.to_string());
+
+    setup.stake("user1", Uint128::from(400u128));
+    setup.stake("user2", Uint128::from(500u128));
+    let err = setup.stake_err("user2", Uint128::from(1u128));
+    assert_eq!(err.to_string(), ContractError::MaxTotalStakeExceeded { max: Uint128::from(1500u128) }.to_string());
+
+    setup.update_stake_limits(None, None);
+    setup.stake("user2", Uint128::from(1000u128));
+
+    let total_staked: TotalStakedResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.staking_addr, &QueryMsg::TotalStaked {})
+        .unwrap();
+    assert_eq!(total_staked.total, Uint128::from(2500u128));
+}
+
+#[test]
+fn test_whitelist_phase() {
+    let mut setup = TestSetup::new();
+    let staking_addr = setup.staking_addr.clone();
+    let staked_amount = Uint128::from(1000u128);
+
+    setup.mint_tokens("user1", staked_amount);
+    setup.approve_tokens("user1", &staking_addr, staked_amount);
+    setup.set_whitelist_phase(true);
+
+    let err = setup.stake_err("user1", staked_amount);
+    assert_eq!(err.to_string(), ContractError::NotWhitelisted {}.to_string());
+
+    setup.update_whitelist(vec![setup.user1.clone()], vec![]);
+    let whitelisted: WhitelistedResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.staking_addr, &QueryMsg::Whitelisted {
+            address: setup.user1.clone(),
+        })
+        .unwrap();
+    assert!(whitelisted.whitelisted);
+
+    setup.stake("user1", staked_amount);
+
+    let config: ConfigResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.staking_addr, &QueryMsg::Config {})
+        .unwrap();
+    assert!(config.whitelist_enabled);
+}
+
+#[test]
+fn test_liquid_staking_receipts() {
+    let mut setup = TestSetup::new_with_receipt_token();
+    let staking_addr = setup.staking_addr.clone();
+    let receipt_addr = setup.receipt_addr.clone().unwrap();
+    let staked_amount = Uint128::from(100000u128);
+
+    let minter: cw20_token::msg::MinterResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&receipt_addr, &cw20_token::msg::QueryMsg::Minter {})
+        .unwrap();
+    assert_eq!(minter.minter, Some(staking_addr.to_string()));
+
+    setup.mint_tokens("user1", staked_amount);
+    setup.set_staking_contract_minter();
+    setup.approve_tokens("user1", &staking_addr, staked_amount);
+    setup.liquid_stake("user1", staked_amount);
+    assert_eq!(setup.balance(&receipt_addr, "user1"), staked_amount);
+
+    setup.advance_time(setup.period);
+
+    let pool: ReceiptPoolResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&staking_addr, &QueryMsg::ReceiptPool {})
+        .unwrap();
+    assert_eq!(pool.total_supply, staked_amount);
+    assert!(pool.exchange_rate > cosmwasm_std::Decimal::one());
+
+    // receipts move freely, the new holder redeems the compounded stake
+    setup.app
+        .execute_contract(
+            setup.addr("user1"),
+            receipt_addr.clone(),
+            &cw20_token::msg::ExecuteMsg::Transfer {
+                recipient: setup.addr("user2").to_string(),
+                amount: staked_amount,
+            },
+            &[],
+        )
+        .unwrap();
+    setup.approve_receipts("user2", staked_amount);
+    setup.liquid_unstake("user2", staked_amount);
+    assert_eq!(setup.balance(&receipt_addr, "user2"), Uint128::zero());
+
+    setup.advance_time(setup.period);
+    setup.withdraw_unbonded("user2").unwrap();
+    let expected = staked_amount.mul_floor(pool.exchange_rate);
+    assert_eq!(setup.balance(&setup.token_addr.clone(), "user2"), expected);
+
+    let pool: ReceiptPoolResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&staking_addr, &QueryMsg::ReceiptPool {})
+        .unwrap();
+    assert_eq!(pool.total_supply, Uint128::zero());
+}
+
+#[test]
+fn test_liquid_stake_counts_towards_address_cap() {
+    let mut setup = TestSetup::new_with_receipt_token();
+    let staking_addr = setup.staking_addr.clone();
+    let receipt_addr = setup.receipt_addr.clone().unwrap();
+    let max = Uint128::from(1000u128);
+
+    setup.update_stake_limits(None, Some(max));
+    setup.mint_tokens("user1", Uint128::from(2000u128));
+    setup.approve_tokens("user1", &staking_addr, Uint128::from(2000u128));
+
+    setup.liquid_stake("user1", Uint128::from(600u128));
+    let err = setup.liquid_stake_err("user1", Uint128::from(401u128));
+    assert_eq!(err.to_string(), ContractError::MaxStakePerAddressExceeded { max }.to_string());
+    let err = setup.stake_err("user1", Uint128::from(401u128));
+    assert_eq!(err.to_string(), ContractError::MaxStakePerAddressExceeded { max }.to_string());
+
+    // the cap follows the receipts, whoever holds them
+    setup.app
+        .execute_contract(
+            setup.addr("user1"),
+            receipt_addr,
+            &cw20_token::msg::ExecuteMsg::Transfer {
+                recipient: setup.addr("user2").to_string(),
+                amount: Uint128::from(600u128),
+            },
+            &[],
+        )
+        .unwrap();
+    setup.stake("user1", Uint128::from(1000u128));
+
+    setup.mint_tokens("user2", Uint128::from(401u128));
+    setup.approve_tokens("user2", &staking_addr, Uint128::from(401u128));
+    let err = setup.liquid_stake_err("user2", Uint128::from(401u128));
+    assert_eq!(err.to_string(), ContractError::MaxStakePerAddressExceeded { max }.to_string());
+    setup.liquid_stake("user2", Uint128::from(400u128));
+}
+
+#[test]
+fn test_liquid_stake_rejected_while_receipts_are_unbacked() {
+    let mut setup = TestSetup::new_with_receipt_token();
+    let staking_addr = setup.staking_addr.clone();
+    let staked_amount = Uint128::from(1000u128);
+
+    // slashing burns receipts along with their stake, so force the state
+    let time = setup.app.block_info().time;
+    RECEIPT_POOL
+        .save(&mut *setup.app.contract_storage_mut(&staking_addr), &ReceiptPool {
+            total_underlying: Uint128::zero(),
+            total_supply: staked_amount,
+            last_compound: time,
+        })
+        .unwrap();
+
+    setup.mint_tokens("user2", staked_amount);
+    setup.approve_tokens("user2", &staking_addr, staked_amount);
+    let err = setup.liquid_stake_err("user2", staked_amount);
+    assert_eq!(err.to_string(), ContractError::InsolventReceiptPool {}.to_string());
+}
+
+#[test]
+fn test_liquid_unstake_waits_for_lockup() {
+    let mut setup = TestSetup::new_with_receipt_token();
+    let staking_addr = setup.staking_addr.clone();
+    let token_addr = setup.token_addr.clone();
+    let staked_amount = Uint128::from(1000u128);
+
+    setup.mint_tokens("user1", staked_amount);
+    setup.approve_tokens("user1", &staking_addr, staked_amount);
+    setup.liquid_stake("user1", staked_amount);
+    setup.approve_receipts("user1", staked_amount);
+    setup.liquid_unstake("user1", staked_amount);
+
+    let unbonding: UnbondingResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&staking_addr, &QueryMsg::Unbonding {
+            address: setup.user1.clone(),
+            start_after: None,
+            limit: None,
+        })
+        .unwrap();
+    assert_eq!(unbonding.unbonding.len(), 1);
+    assert_eq!(unbonding.unbonding[0].amount, staked_amount);
+
+    let err = setup.withdraw_unbonded("user1").unwrap_err();
+    assert_eq!(err.to_string(), ContractError::NothingToWithdraw {}.to_string());
+    assert_eq!(setup.balance(&token_addr, "user1"), Uint128::zero());
+
+    setup.advance_time(setup.period - 1);
+    assert!(setup.withdraw_unbonded("user1").is_err());
+
+    setup.advance_time(1);
+    setup.withdraw_unbonded("user1").unwrap();
+    assert_eq!(setup.balance(&token_addr, "user1"), staked_amount);
+}
+
+#[test]
+fn test_unbonding_entries_are_capped() {
+    let mut setup = TestSetup::new_with_receipt_token();
+    let staking_addr = setup.staking_addr.clone();
+    let staked_amount = Uint128::from(31u128);
+
+    setup.mint_tokens("user1", staked_amount);
+    setup.approve_tokens("user1", &staking_addr, staked_amount);
+    setup.liquid_stake("user1", staked_amount);
+    setup.approve_receipts("user1", staked_amount);
+    for _ in 0..31 {
+        setup.advance_time(1);
+        setup.liquid_unstake("user1", Uint128::one());
+    }
+
+    let unbonding: UnbondingResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&staking_addr, &QueryMsg::Unbonding {
+            address: setup.user1.clone(),
+            start_after: Some(28),
+            limit: None,
+        })
+        .unwrap();
+    // the last redemption joined the 30th entry and moved its release back
+    assert_eq!(unbonding.unbonding.len(), 1);
+    assert_eq!(unbonding.unbonding[0].amount, Uint128::from(2u128));
+    assert_eq!(
+        unbonding.unbonding[0].release_at,
+        setup.app.block_info().time.seconds() + setup.period,
+    );
+}
+
+#[test]
+fn test_claimed_rewards_vest() {
+    let mut setup = TestSetup::new();
+    let staking_addr = setup.staking_addr.clone();
+    let token_addr = setup.token_addr.clone();
+    let staked_amount = Uint128::from(1_000_000u128);
+    let cliff = 100;
+    let duration = 1000;
+
+    setup.mint_tokens("user1", staked_amount);
+    setup.approve_tokens("user1", &staking_addr, staked_amount);
+    setup.stake("user1", staked_amount);
+    setup.set_staking_contract_minter();
+    setup.update_reward_vesting(Some(RewardVesting { cliff, duration }));
+
+    setup.advance_time(setup.period);
+    setup.claim_rewards("user1");
+    assert_eq!(setup.balance(&token_addr, "user1"), Uint128::zero());
+
+    let vesting: VestingResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&staking_addr, &QueryMsg::Vesting { address: setup.user1.clone() })
+        .unwrap();
+    let reward = vesting.total;
+    assert!(!reward.is_zero());
+    assert_eq!(vesting.locked, reward);
+    assert_eq!(vesting.vested, Uint128::zero());
+    assert_eq!(vesting.end_time - vesting.start_time, duration);
+
+    let err = setup.app
+        .execute_contract(
+            setup.addr("user1"),
+            staking_addr.clone(),
+            &staking::msg::ExecuteMsg::WithdrawVested {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap().to_string(),
+        ContractError::NothingToWithdraw {}.to_string(),
+    );
+
+    setup.advance_time(duration / 2);
+    setup.withdraw_vested("user1");
+    assert_eq!(setup.balance(&token_addr, "user1"), reward.multiply_ratio(1u128, 2u128));
+
+    setup.advance_time(duration);
+    setup.withdraw_vested("user1");
+    assert_eq!(setup.balance(&token_addr, "user1"), reward);
+
+    let vesting: VestingResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&staking_addr, &QueryMsg::Vesting { address: setup.user1.clone() })
+        .unwrap();
+    assert_eq!(vesting.total, Uint128::zero());
+}
+
+#[test]
+fn test_claims_inside_cliff_keep_their_schedule() {
+    let mut setup = TestSetup::new();
+    let staking_addr = setup.staking_addr.clone();
+    let token_addr = setup.token_addr.clone();
+    let staked_amount = Uint128::from(1_000_000u128);
+    let period = setup.period;
+    let cliff = 2 * period;
+    let duration = 4 * period;
+
+    setup.mint_tokens("user1", staked_amount);
+    setup.approve_tokens("user1", &staking_addr, staked_amount);
+    setup.stake("user1", staked_amount);
+    setup.set_staking_contract_minter();
+    setup.update_reward_vesting(Some(RewardVesting { cliff, duration }));
+
+    let vesting = |setup: &TestSetup| -> VestingResponse {
+        setup.app
+            .wrap()
+            .query_wasm_smart(&staking_addr, &QueryMsg::Vesting { address: setup.user1.clone() })
+            .unwrap()
+    };
+
+    setup.advance_time(period);
+    setup.claim_rewards("user1");
+    let first = vesting(&setup);
+
+    // the second claim lands inside the first tranche's cliff
+    setup.advance_time(period);
+    setup.claim_rewards("user1");
+    let both = vesting(&setup);
+    let second = both.total - first.total;
+    assert!(!second.is_zero());
+    assert_eq!(both.start_time, first.start_time);
+    assert_eq!(both.cliff_end, first.cliff_end);
+    assert_eq!(both.end_time, first.end_time + period);
+
+    // past the first cliff only, the first tranche is half vested
+    setup.advance_time(period);
+    setup.withdraw_vested("user1");
+    assert_eq!(setup.balance(&token_addr, "user1"), first.total.multiply_ratio(1u128, 2u128));
+
+    setup.advance_time(duration);
+    setup.withdraw_vested("user1");
+    assert_eq!(setup.balance(&token_addr, "user1"), both.total);
+    assert_eq!(vesting(&setup).total, Uint128::zero());
+}
+
+#[test]
+fn test_vesting_tranches_are_capped() {
+    let mut setup = TestSetup::new();
+    let staking_addr = setup.staking_addr.clone();
+    let token_addr = setup.token_addr.clone();
+    let staked_amount = Uint128::from(1_000_000u128);
+    let period = setup.period;
+    // long enough that no tranche finishes while the claims pile up
+    let duration = 40 * period;
+
+    setup.mint_tokens("user1", staked_amount);
+    setup.approve_tokens("user1", &staking_addr, staked_amount);
+    setup.stake("user1", staked_amount);
+    setup.set_staking_contract_minter();
+    setup.update_reward_vesting(Some(RewardVesting { cliff: 0, duration }));
+
+    let tranches = |setup: &TestSetup| -> usize {
+        let storage = setup.app.contract_storage(&staking_addr);
+        VESTING
+            .prefix(&setup.addr("user1"))
+            .keys(&*storage, None, None, Order::Ascending)
+            .count()
+    };
+
+    for _ in 0..30 {
+        setup.advance_time(period);
+        setup.claim_rewards("user1");
+    }
+    assert_eq!(tranches(&setup), 30);
+    assert_eq!(setup.balance(&token_addr, "user1"), Uint128::zero());
+
+    // at the limit the vested rewards are paid out and the new one joins the newest tranche
+    setup.advance_time(period);
+    setup.claim_rewards("user1");
+    assert_eq!(tranches(&setup), 30);
+
+    let paid = setup.balance(&token_addr, "user1");
+    assert!(!paid.is_zero());
+
+    let vesting: VestingResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&staking_addr, &QueryMsg::Vesting { address: setup.user1.clone() })
+        .unwrap();
+    let token_info: cw20_token::msg::TokenInfoResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&token_addr, &cw20_token::msg::QueryMsg::TokenInfo {})
+        .unwrap();
+    let rewards = token_info.total_supply - staked_amount;
+    assert_eq!(paid + vesting.total - vesting.released, rewards);
+    assert_eq!(vesting.end_time, setup.app.block_info().time.seconds() + duration);
+}
+
+#[test]
+fn test_slash_stake() {
+    let mut setup = TestSetup::new();
+    let staking_addr = setup.staking_addr.clone();
+    let token_addr = setup.token_addr.clone();
+    let staked_amount = Uint128::from(1000u128);
+
+    setup.mint_tokens("user1", staked_amount);
+    setup.approve_tokens("user1", &staking_addr, staked_amount);
+    setup.stake("user1", staked_amount);
+
+    setup.app
+        .execute_contract(
+            setup.get_owner_addr(),
+            staking_addr.clone(),
+            &staking::msg::ExecuteMsg::UpdateSlashing {
+                slasher: Some(setup.addr("slasher").to_string()),
+                slash_recipient: Some(setup.addr("treasury").to_string()),
+            },
+            &[],
+        )
+        .unwrap();
+
+    let user1 = setup.user1.clone();
+    let slash = |fraction: &str| staking::msg::ExecuteMsg::Slash {
+        address: user1.clone(),
+        fraction: fraction.parse().unwrap(),
+        reason: "downtime".to_string(),
+    };
+
+    let err = setup.app
+        .execute_contract(setup.addr("user1"), staking_addr.clone(), &slash("0.1"), &[])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap().to_string(),
+        ContractError::Unauthorized {}.to_string(),
+    );
+
+    let err = setup.app
+        .execute_contract(setup.addr("slasher"), staking_addr.clone(), &slash("1.5"), &[])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap().to_string(),
+        ContractError::InvalidSlashFraction {}.to_string(),
+    );
+
+    setup.app
+        .execute_contract(setup.addr("slasher"), staking_addr.clone(), &slash("0.1"), &[])
+        .unwrap();
+    setup.app
+        .execute_contract(setup.get_owner_addr(), staking_addr.clone(), &slash("0.5"), &[])
+        .unwrap();
+
+    let staked_info: StakeResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&staking_addr, &QueryMsg::Stake { address: setup.user1.clone() })
+        .unwrap();
+    let total_staked: TotalStakedResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&staking_addr, &QueryMsg::TotalStaked {})
+        .unwrap();
+    assert_eq!(staked_info.amount, Uint128::from(450u128));
+    assert_eq!(total_staked.total, Uint128::from(450u128));
+    assert_eq!(setup.balance(&token_addr, "treasury"), Uint128::from(550u128));
+
+    let history: SlashHistoryResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&staking_addr, &QueryMsg::SlashHistory {
+            address: setup.user1.clone(),
+            start_after: None,
+            limit: None,
+        })
+        .unwrap();
+    assert_eq!(history.slashes.len(), 2);
+    assert_eq!(history.slashes[0].amount, Uint128::from(100u128));
+    assert_eq!(history.slashes[1].amount, Uint128::from(450u128));
+    assert_eq!(history.slashes[1].reason, "downtime");
+
+    let history: SlashHistoryResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&staking_addr, &QueryMsg::SlashHistory {
+            address: setup.user1.clone(),
+            start_after: Some(0),
+            limit: None,
+        })
+        .unwrap();
+    assert_eq!(history.slashes.len(), 1);
+    assert_eq!(history.slashes[0].id, 1);
+}
+#[test]
+fn test_slash_liquid_stake() {
+    let mut setup = TestSetup::new_with_receipt_token();
+    let staking_addr = setup.staking_addr.clone();
+    let token_addr = setup.token_addr.clone();
+    let receipt_addr = setup.receipt_addr.clone().unwrap();
+    let staked_amount = Uint128::from(1000u128);
+
+    for user in ["user1", "user2"] {
+        setup.mint_tokens(user, staked_amount);
+        setup.approve_tokens(user, &staking_addr, staked_amount);
+        setup.liquid_stake(user, staked_amount);
+    }
+    // user1 starts redeeming right before the slash
+    setup.approve_receipts("user1", Uint128::from(400u128));
+    setup.liquid_unstake("user1", Uint128::from(400u128));
+
+    setup.app
+        .execute_contract(
+            setup.get_owner_addr(),
+            staking_addr.clone(),
+            &staking::msg::ExecuteMsg::UpdateSlashing {
+                slasher: None,
+                slash_recipient: Some(setup.addr("treasury").to_string()),
+            },
+            &[],
+        )
+        .unwrap();
+    setup.app
+        .execute_contract(
+            setup.get_owner_addr(),
+            staking_addr.clone(),
+            &staking::msg::ExecuteMsg::Slash {
+                address: setup.user1.clone(),
+                fraction: "0.1".parse().unwrap(),
+                reason: "double sign".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    // 60 from the 600 receipts user1 holds and 40 from the 400 unbonding
+    assert_eq!(setup.balance(&token_addr, "treasury"), Uint128::from(100u128));
+    assert_eq!(setup.balance(&receipt_addr, "user1"), Uint128::from(540u128));
+    assert_eq!(setup.balance(&receipt_addr, "user2"), staked_amount);
+
+    let unbonding: UnbondingResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&staking_addr, &QueryMsg::Unbonding {
+            address: setup.user1.clone(),
+            start_after: None,
+            limit: None,
+        })
+        .unwrap();
+    assert_eq!(unbonding.unbonding[0].amount, Uint128::from(360u128));
+
+    // the other holder's receipts keep their value
+    let pool: ReceiptPoolResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&staking_addr, &QueryMsg::ReceiptPool {})
+        .unwrap();
+    assert_eq!(pool.total_underlying, Uint128::from(1540u128));
+    assert_eq!(pool.total_supply, Uint128::from(1540u128));
+    assert_eq!(pool.exchange_rate, cosmwasm_std::Decimal::one());
+
+    let history: SlashHistoryResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&staking_addr, &QueryMsg::SlashHistory {
+            address: setup.user1.clone(),
+            start_after: None,
+            limit: None,
+        })
+        .unwrap();
+    assert_eq!(history.slashes[0].amount, Uint128::from(100u128));
+
+    setup.approve_receipts("user2", staked_amount);
+    setup.liquid_unstake("user2", staked_amount);
+    setup.advance_time(setup.period);
+    setup.withdraw_unbonded("user1").unwrap();
+    setup.withdraw_unbonded("user2").unwrap();
+    assert_eq!(setup.balance(&token_addr, "user1"), Uint128::from(360u128));
+    assert_eq!(setup.balance(&token_addr, "user2"), staked_amount);
+}
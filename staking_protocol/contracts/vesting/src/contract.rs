@@ -0,0 +1,307 @@
+use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, entry_point, to_json_binary, from_json, StdResult, WasmMsg, Uint128, Addr, Order, Empty};
+use cw20::Cw20ReceiveMsg;
+use cw_ownable::initialize_owner;
+use cw_storage_plus::Bound;
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, ExecuteMsg, GrantResponse, GrantsResponse, InstantiateMsg, QueryMsg, ReceiveMsg,
+};
+use crate::state::{Config, Grant, Schedule, CONFIG, GRANTS, GRANTS_BY_BENEFICIARY, LATEST_GRANT};
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+#[entry_point]
+pub fn instantiate (
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    initialize_owner(deps.storage, deps.api, Some(&msg.owner))?;
+
+    let token_address = deps.api.addr_validate(&msg.token_address)?;
+    CONFIG.save(deps.storage, &Config { token_address: token_address.clone() })?;
+    LATEST_GRANT.save(deps.storage, &0)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("token_address", token_address))
+}
+
+#[entry_point]
+pub fn execute (
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => {
+            execute_receive(deps, info, msg)
+        }
+        ExecuteMsg::Claim { id } => {
+            execute_claim(deps, env, info, id)
+        }
+        ExecuteMsg::Revoke { id, recipient } => {
+            execute_revoke(deps, env, info, id, recipient)
+        }
+        ExecuteMsg::UpdateOwnership(action) => {
+            cw_ownable::update_ownership(deps, &env.block, &info.sender, action)?;
+            Ok(Response::new().add_attribute("action", "update_ownership"))
+        }
+    }
+}
+
+#[entry_point]
+pub fn query (
+    deps: Deps,
+    env: Env,
+    msg: QueryMsg,
+) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
+        QueryMsg::Grant {id} => {
+            let grant = GRANTS.load(deps.storage, id)?;
+            to_json_binary(&grant_response(&env, id, grant))
+        }
+        QueryMsg::Grants {start_after, limit} => {
+            to_json_binary(&query_grants(deps, env, start_after, limit)?)
+        }
+        QueryMsg::GrantsByBeneficiary {beneficiary, start_after, limit} => {
+            to_json_binary(&query_grants_by_beneficiary(deps, env, beneficiary, start_after, limit)?)
+        }
+        QueryMsg::Ownership {} => {
+            to_json_binary(&cw_ownable::get_ownership(deps.storage)?)
+        }
+    }
+}
+
+pub fn execute_receive(
+    deps: DepsMut,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.token_address {
+        return Err(ContractError::WrongToken {});
+    }
+    // grants are funded by the owner, the token only relays the sender
+    let sender = deps.api.addr_validate(&wrapper.sender)?;
+    cw_ownable::assert_owner(deps.storage, &sender)?;
+
+    let ReceiveMsg::CreateGrant { beneficiary, schedule, revocable } = from_json(&wrapper.msg)?;
+    let beneficiary = deps.api.addr_validate(&beneficiary)?;
+    let amount = wrapper.amount;
+    if amount.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+    validate_schedule(&schedule, amount)?;
+
+    let id = LATEST_GRANT.update(deps.storage, |id| -> StdResult<_> { Ok(id + 1) })?;
+    GRANTS.save(deps.storage, id, &Grant {
+        beneficiary: beneficiary.clone(),
+        amount,
+        claimed: Uint128::zero(),
+        schedule,
+        revocable,
+        revoked_at: None,
+    })?;
+    GRANTS_BY_BENEFICIARY.save(deps.storage, (&beneficiary, id), &Empty {})?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_grant")
+        .add_attribute("id", id.to_string())
+        .add_attribute("beneficiary", beneficiary)
+        .add_attribute("amount", amount)
+        .add_attribute("revocable", revocable.to_string()))
+}
+
+pub fn execute_claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let mut grant = GRANTS.load(deps.storage, id)?;
+    if info.sender != grant.beneficiary {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let amount = grant.vested(env.block.time.seconds()) - grant.claimed;
+    if amount.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+    grant.claimed += amount;
+    GRANTS.save(deps.storage, id, &grant)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    Ok(Response::new()
+        .add_message(transfer_msg(&config.token_address, &grant.beneficiary, amount)?)
+        .add_attribute("action", "claim")
+        .add_attribute("id", id.to_string())
+        .add_attribute("beneficiary", grant.beneficiary)
+        .add_attribute("amount", amount))
+}
+
+pub fn execute_revoke(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+    recipient: Option<String>,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let mut grant = GRANTS.load(deps.storage, id)?;
+    if !grant.revocable {
+        return Err(ContractError::NotRevocable { id });
+    }
+    if grant.revoked_at.is_some() {
+        return Err(ContractError::AlreadyRevoked { id });
+    }
+
+    let recipient = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => info.sender,
+    };
+
+    // settles the beneficiary now, nothing is left to claim afterwards
+    let now = env.block.time.seconds();
+    let vested = grant.vested(now);
+    let payout = vested - grant.claimed;
+    let returned = grant.amount - vested;
+    grant.claimed = vested;
+    grant.revoked_at = Some(now);
+    GRANTS.save(deps.storage, id, &grant)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let mut response = Response::new();
+    if !payout.is_zero() {
+        response = response.add_message(transfer_msg(&config.token_address, &grant.beneficiary, payout)?);
+    }
+    if !returned.is_zero() {
+        response = response.add_message(transfer_msg(&config.token_address, &recipient, returned)?);
+    }
+
+    Ok(response
+        .add_attribute("action", "revoke")
+        .add_attribute("id", id.to_string())
+        .add_attribute("beneficiary", grant.beneficiary)
+        .add_attribute("vested", payout)
+        .add_attribute("recipient", recipient)
+        .add_attribute("returned", returned))
+}
+
+fn validate_schedule(schedule: &Schedule, amount: Uint128) -> Result<(), ContractError> {
+    match schedule {
+        Schedule::Linear { start_time, end_time } => {
+            if start_time >= end_time {
+                return Err(ContractError::InvalidSchedule {});
+            }
+        }
+        Schedule::Cliff { start_time, cliff_time, end_time } => {
+            if start_time >= end_time || cliff_time < start_time || cliff_time > end_time {
+                return Err(ContractError::InvalidSchedule {});
+            }
+        }
+        Schedule::Steps { steps } => {
+            if steps.is_empty()
+                || steps.iter().any(|step| step.amount.is_zero())
+                || steps.windows(2).any(|pair| pair[0].time >= pair[1].time)
+            {
+                return Err(ContractError::InvalidSchedule {});
+            }
+            let total: Uint128 = steps.iter().map(|step| step.amount).sum();
+            if total != amount {
+                return Err(ContractError::StepsMismatch { total });
+            }
+        }
+    }
+    Ok(())
+}
+
+fn transfer_msg(
+    token: &Addr,
+    recipient: &Addr,
+    amount: Uint128,
+) -> StdResult<WasmMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_json_binary(&cw20_token::msg::ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount,
+        })?,
+        funds: vec![],
+    })
+}
+
+fn query_config(
+    deps: Deps,
+) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        token_address: config.token_address.to_string(),
+    })
+}
+
+fn query_grants(
+    deps: Deps,
+    env: Env,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<GrantsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let grants = GRANTS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (id, grant) = item?;
+            Ok(grant_response(&env, id, grant))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(GrantsResponse { grants })
+}
+
+fn query_grants_by_beneficiary(
+    deps: Deps,
+    env: Env,
+    beneficiary: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<GrantsResponse> {
+    let beneficiary = deps.api.addr_validate(&beneficiary)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let grants = GRANTS_BY_BENEFICIARY
+        .prefix(&beneficiary)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let id = item?;
+            let grant = GRANTS.load(deps.storage, id)?;
+            Ok(grant_response(&env, id, grant))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(GrantsResponse { grants })
+}
+
+fn grant_response(env: &Env, id: u64, grant: Grant) -> GrantResponse {
+    let vested = grant.vested(env.block.time.seconds());
+    GrantResponse {
+        id,
+        beneficiary: grant.beneficiary.to_string(),
+        amount: grant.amount,
+        vested,
+        claimed: grant.claimed,
+        claimable: vested - grant.claimed,
+        schedule: grant.schedule,
+        revocable: grant.revocable,
+        revoked_at: grant.revoked_at,
+    }
+}
\ No newline at end of file
@@ -0,0 +1,36 @@
+use cosmwasm_std::{StdError, Uint128};
+use thiserror::Error;
+use cw_ownable::OwnershipError;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Ownership(#[from] OwnershipError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Only the configured token can be vested")]
+    WrongToken {},
+
+    #[error("Cannot vest zero tokens")]
+    ZeroAmount {},
+
+    #[error("Invalid schedule, times must increase and step amounts must be non zero")]
+    InvalidSchedule {},
+
+    #[error("Steps add up to {total}, not the amount sent")]
+    StepsMismatch { total: Uint128 },
+
+    #[error("Nothing to claim")]
+    NothingToClaim {},
+
+    #[error("Grant {id} is not revocable")]
+    NotRevocable { id: u64 },
+
+    #[error("Grant {id} is already revoked")]
+    AlreadyRevoked { id: u64 },
+}
\ No newline at end of file
@@ -0,0 +1,82 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Uint128;
+use cw20::Cw20ReceiveMsg;
+use cw_ownable::Action;
+
+use crate::state::Schedule;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub owner: String,
+    pub token_address: String,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Owner sends tokens with a `ReceiveMsg` to create a grant
+    Receive(Cw20ReceiveMsg),
+    /// Pays out what has vested so far, beneficiary only
+    Claim {
+        id: u64,
+    },
+    /// Stops a revocable grant, the beneficiary gets what has vested and
+    /// the rest goes to the recipient, the owner by default
+    Revoke {
+        id: u64,
+        recipient: Option<String>,
+    },
+    UpdateOwnership(Action),
+}
+
+#[cw_serde]
+pub enum ReceiveMsg {
+    /// Vests the whole amount sent for the beneficiary
+    CreateGrant {
+        beneficiary: String,
+        schedule: Schedule,
+        revocable: bool,
+    },
+}
+
+#[derive(QueryResponses)]
+#[cw_serde]
+pub enum QueryMsg {
+    #[returns(ConfigResponse)]
+    Config {},
+
+    #[returns(GrantResponse)]
+    Grant {id: u64},
+
+    #[returns(GrantsResponse)]
+    Grants {start_after: Option<u64>, limit: Option<u32>},
+
+    #[returns(GrantsResponse)]
+    GrantsByBeneficiary {beneficiary: String, start_after: Option<u64>, limit: Option<u32>},
+
+    #[returns(cw_ownable::Ownership<cosmwasm_std::Addr>)]
+    Ownership {},
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub token_address: String,
+}
+
+#[cw_serde]
+pub struct GrantResponse {
+    pub id: u64,
+    pub beneficiary: String,
+    pub amount: Uint128,
+    pub vested: Uint128,
+    pub claimed: Uint128,
+    /// vested - claimed
+    pub claimable: Uint128,
+    pub schedule: Schedule,
+    pub revocable: bool,
+    pub revoked_at: Option<u64>,
+}
+
+#[cw_serde]
+pub struct GrantsResponse {
+    pub grants: Vec<GrantResponse>,
+}
\ No newline at end of file
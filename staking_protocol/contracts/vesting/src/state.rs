@@ -0,0 +1,80 @@
+use cosmwasm_std::{Addr, Empty, Uint128};
+use cw_storage_plus::{Item, Map};
+use cosmwasm_schema::cw_serde;
+
+#[cw_serde]
+pub struct Config {
+    pub token_address: Addr,
+}
+
+/// When a grant unlocks, all times are unix seconds
+#[cw_serde]
+pub enum Schedule {
+    /// Vests evenly from `start_time` to `end_time`
+    Linear { start_time: u64, end_time: u64 },
+    /// Linear from `start_time` to `end_time`, but nothing unlocks before
+    /// `cliff_time`, the part accrued by then unlocks at once
+    Cliff { start_time: u64, cliff_time: u64, end_time: u64 },
+    /// Each step unlocks its amount at its time, the amounts add up to
+    /// the grant
+    Steps { steps: Vec<Step> },
+}
+
+#[cw_serde]
+pub struct Step {
+    pub time: u64,
+    pub amount: Uint128,
+}
+
+impl Schedule {
+    pub fn vested(&self, total: Uint128, now: u64) -> Uint128 {
+        match self {
+            Schedule::Linear { start_time, end_time } => linear(total, *start_time, *end_time, now),
+            Schedule::Cliff { start_time, cliff_time, end_time } => {
+                if now < *cliff_time {
+                    Uint128::zero()
+                } else {
+                    linear(total, *start_time, *end_time, now)
+                }
+            }
+            Schedule::Steps { steps } => steps
+                .iter()
+                .filter(|step| step.time <= now)
+                .map(|step| step.amount)
+                .sum(),
+        }
+    }
+}
+
+fn linear(total: Uint128, start_time: u64, end_time: u64, now: u64) -> Uint128 {
+    if now <= start_time {
+        Uint128::zero()
+    } else if now >= end_time {
+        total
+    } else {
+        total.multiply_ratio(now - start_time, end_time - start_time)
+    }
+}
+
+#[cw_serde]
+pub struct Grant {
+    pub beneficiary: Addr,
+    pub amount: Uint128,
+    pub claimed: Uint128,
+    pub schedule: Schedule,
+    pub revocable: bool,
+    // vesting stops here once the owner revoked the grant
+    pub revoked_at: Option<u64>,
+}
+
+impl Grant {
+    pub fn vested(&self, now: u64) -> Uint128 {
+        let now = self.revoked_at.map_or(now, |revoked_at| revoked_at.min(now));
+        self.schedule.vested(self.amount, now)
+    }
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const LATEST_GRANT: Item<u64> = Item::new("latest_grant");
+pub const GRANTS: Map<u64, Grant> = Map::new("grants");
+pub const GRANTS_BY_BENEFICIARY: Map<(&Addr, u64), Empty> = Map::new("grants_by_beneficiary");
\ No newline at end of file
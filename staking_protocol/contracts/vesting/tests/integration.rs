@@ -0,0 +1,371 @@
+use cosmwasm_std::{to_json_binary, Addr, Empty, Uint128};
+use cw20::Cw20ReceiveMsg;
+use cw_multi_test::{App, ContractWrapper, Executor};
+
+use vesting::ContractError;
+use vesting::msg::{ExecuteMsg, GrantResponse, GrantsResponse, InstantiateMsg, QueryMsg, ReceiveMsg};
+use vesting::state::{Schedule, Step};
+
+fn cw20_contract() -> Box<dyn cw_multi_test::Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw20_token::contract::execute,
+        cw20_token::contract::instantiate,
+        cw20_token::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn vesting_contract() -> Box<dyn cw_multi_test::Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        vesting::contract::execute,
+        vesting::contract::instantiate,
+        vesting::contract::query,
+    );
+    Box::new(contract)
+}
+
+struct TestSetup {
+    app: App,
+    token_addr: Addr,
+    vesting_addr: Addr,
+    owner: Addr,
+}
+
+impl TestSetup {
+    fn new() -> Self {
+        let mut app = App::default();
+        let owner = app.api().addr_make("owner");
+        let cw20_code_id = app.store_code(cw20_contract());
+        let vesting_code_id = app.store_code(vesting_contract());
+
+        let token_addr = app
+            .instantiate_contract(
+                cw20_code_id,
+                owner.clone(),
+                &cw20_token::msg::InstantiateMsg {
+                    name: "Test Token".to_string(),
+                    symbol: "TST".to_string(),
+                    decimals: 18,
+                    initial_balances: vec![],
+                    owner: None,
+                    minter: Some(owner.to_string()),
+                    cap: None,
+                    marketing: None,
+                    compliance: false,
+                    transfer_fee_bps: None,
+                    fee_recipient: None,
+                },
+                &[],
+                "Test Token",
+                None,
+            )
+            .unwrap();
+
+        let vesting_addr = app
+            .instantiate_contract(
+                vesting_code_id,
+                owner.clone(),
+                &InstantiateMsg {
+                    owner: owner.to_string(),
+                    token_address: token_addr.to_string(),
+                },
+                &[],
+                "Vesting",
+                None,
+            )
+            .unwrap();
+
+        let mut setup = TestSetup {
+            app,
+            token_addr,
+            vesting_addr,
+            owner,
+        };
+        let owner = setup.owner.clone();
+        setup.execute_token(&owner, &cw20_token::msg::ExecuteMsg::Mint {
+            recipient: owner.to_string(),
+            amount: Uint128::from(10_000u128),
+        })
+        .unwrap();
+        setup
+    }
+
+    fn addr(&self, name: &str) -> Addr {
+        self.app.api().addr_make(name)
+    }
+
+    fn now(&self) -> u64 {
+        self.app.block_info().time.seconds()
+    }
+
+    fn advance_time(&mut self, seconds: u64) {
+        self.app.update_block(|block| {
+            block.time = block.time.plus_seconds(seconds);
+            block.height += 1;
+        });
+    }
+
+    fn execute(&mut self, sender: &Addr, msg: &ExecuteMsg) -> Result<(), ContractError> {
+        self.app
+            .execute_contract(sender.clone(), self.vesting_addr.clone(), msg, &[])
+            .map(|_| ())
+            .map_err(|err| err.downcast().unwrap())
+    }
+
+    fn execute_token(&mut self, sender: &Addr, msg: &cw20_token::msg::ExecuteMsg) -> Result<(), ContractError> {
+        self.app
+            .execute_contract(sender.clone(), self.token_addr.clone(), msg, &[])
+            .map(|_| ())
+            .map_err(|err| err.downcast().unwrap())
+    }
+
+    /// Funds a grant from `sender` through the token's Send
+    fn create_grant(
+        &mut self,
+        sender: &Addr,
+        beneficiary: &Addr,
+        amount: u128,
+        schedule: Schedule,
+        revocable: bool,
+    ) -> Result<(), ContractError> {
+        let msg = cw20_token::msg::ExecuteMsg::Send {
+            contract: self.vesting_addr.to_string(),
+            amount: Uint128::from(amount),
+            msg: to_json_binary(&ReceiveMsg::CreateGrant {
+                beneficiary: beneficiary.to_string(),
+                schedule,
+                revocable,
+            })
+            .unwrap(),
+        };
+        self.execute_token(sender, &msg)
+    }
+
+    fn claim(&mut self, sender: &Addr, id: u64) -> Result<(), ContractError> {
+        self.execute(sender, &ExecuteMsg::Claim { id })
+    }
+
+    fn balance(&self, address: &Addr) -> Uint128 {
+        let res: cw20_token::msg::BalanceResponse = self.app
+            .wrap()
+            .query_wasm_smart(&self.token_addr, &cw20_token::msg::QueryMsg::Balance {
+                address: address.to_string(),
+            })
+            .unwrap();
+        res.balance
+    }
+
+    fn grant(&self, id: u64) -> GrantResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.vesting_addr, &QueryMsg::Grant { id })
+            .unwrap()
+    }
+}
+
+#[test]
+fn test_linear_grant_and_claim() {
+    let mut setup = TestSetup::new();
+    let owner = setup.owner.clone();
+    let user1 = setup.addr("user1");
+    let user2 = setup.addr("user2");
+    let start_time = setup.now();
+    let schedule = Schedule::Linear { start_time, end_time: start_time + 1000 };
+
+    // only the owner funds grants, and only with the configured token
+    setup.execute_token(&owner, &cw20_token::msg::ExecuteMsg::Transfer {
+        recipient: user2.to_string(),
+        amount: Uint128::from(500u128),
+    })
+    .unwrap();
+    let err = setup.create_grant(&user2, &user1, 500, schedule.clone(), false).unwrap_err();
+    assert_eq!(err.to_string(), ContractError::Ownership(cw_ownable::OwnershipError::NotOwner).to_string());
+
+    let err = setup.execute(&owner, &ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: owner.to_string(),
+        amount: Uint128::from(500u128),
+        msg: to_json_binary(&ReceiveMsg::CreateGrant {
+            beneficiary: user1.to_string(),
+            schedule: schedule.clone(),
+            revocable: false,
+        })
+        .unwrap(),
+    }))
+    .unwrap_err();
+    assert_eq!(err.to_string(), ContractError::WrongToken {}.to_string());
+
+    let err = setup.create_grant(&owner, &user1, 1000, Schedule::Linear { start_time, end_time: start_time }, false).unwrap_err();
+    assert_eq!(err.to_string(), ContractError::InvalidSchedule {}.to_string());
+
+    setup.create_grant(&owner, &user1, 1000, schedule, false).unwrap();
+    assert_eq!(setup.balance(&setup.vesting_addr.clone()), Uint128::from(1000u128));
+
+    let err = setup.claim(&user1, 1).unwrap_err();
+    assert_eq!(err.to_string(), ContractError::NothingToClaim {}.to_string());
+
+    setup.advance_time(250);
+    let err = setup.claim(&user2, 1).unwrap_err();
+    assert_eq!(err.to_string(), ContractError::Unauthorized {}.to_string());
+    setup.claim(&user1, 1).unwrap();
+    assert_eq!(setup.balance(&user1), Uint128::from(250u128));
+
+    let grant = setup.grant(1);
+    assert_eq!(grant.vested, Uint128::from(250u128));
+    assert_eq!(grant.claimable, Uint128::zero());
+
+    // past the end everything is claimable
+    setup.advance_time(5000);
+    setup.claim(&user1, 1).unwrap();
+    assert_eq!(setup.balance(&user1), Uint128::from(1000u128));
+    assert_eq!(setup.balance(&setup.vesting_addr.clone()), Uint128::zero());
+}
+
+#[test]
+fn test_cliff_and_step_schedules() {
+    let mut setup = TestSetup::new();
+    let owner = setup.owner.clone();
+    let user1 = setup.addr("user1");
+    let user2 = setup.addr("user2");
+    let start_time = setup.now();
+
+    let err = setup.create_grant(&owner, &user1, 1000, Schedule::Cliff {
+        start_time,
+        cliff_time: start_time + 2000,
+        end_time: start_time + 1000,
+    }, false)
+    .unwrap_err();
+    assert_eq!(err.to_string(), ContractError::InvalidSchedule {}.to_string());
+
+    setup.create_grant(&owner, &user1, 1000, Schedule::Cliff {
+        start_time,
+        cliff_time: start_time + 400,
+        end_time: start_time + 1000,
+    }, false)
+    .unwrap();
+
+    let steps = |amounts: [u128; 3]| Schedule::Steps {
+        steps: amounts
+            .iter()
+            .enumerate()
+            .map(|(i, amount)| Step { time: start_time + 100 * (i as u64 + 1), amount: Uint128::from(*amount) })
+            .collect(),
+    };
+    let err = setup.create_grant(&owner, &user2, 600, steps([100, 200, 200]), false).unwrap_err();
+    assert_eq!(err.to_string(), ContractError::StepsMismatch { total: Uint128::from(500u128) }.to_string());
+
+    let err = setup.create_grant(&owner, &user2, 300, Schedule::Steps {
+        steps: vec![
+            Step { time: start_time + 200, amount: Uint128::from(100u128) },
+            Step { time: start_time + 100, amount: Uint128::from(200u128) },
+        ],
+    }, false)
+    .unwrap_err();
+    assert_eq!(err.to_string(), ContractError::InvalidSchedule {}.to_string());
+
+    setup.create_grant(&owner, &user2, 600, steps([100, 200, 300]), false).unwrap();
+
+    setup.advance_time(250);
+    assert_eq!(setup.grant(1).vested, Uint128::zero());
+    assert_eq!(setup.grant(2).vested, Uint128::from(300u128));
+
+    // at the cliff everything accrued since the start unlocks at once
+    setup.advance_time(150);
+    assert_eq!(setup.grant(1).vested, Uint128::from(400u128));
+    setup.claim(&user1, 1).unwrap();
+    setup.claim(&user2, 2).unwrap();
+    assert_eq!(setup.balance(&user1), Uint128::from(400u128));
+    assert_eq!(setup.balance(&user2), Uint128::from(600u128));
+}
+
+#[test]
+fn test_revoke() {
+    let mut setup = TestSetup::new();
+    let owner = setup.owner.clone();
+    let user1 = setup.addr("user1");
+    let treasury = setup.addr("treasury");
+    let start_time = setup.now();
+    let schedule = Schedule::Linear { start_time, end_time: start_time + 1000 };
+
+    setup.create_grant(&owner, &user1, 1000, schedule.clone(), false).unwrap();
+    setup.create_grant(&owner, &user1, 1000, schedule, true).unwrap();
+
+    let err = setup.execute(&owner, &ExecuteMsg::Revoke { id: 1, recipient: None }).unwrap_err();
+    assert_eq!(err.to_string(), ContractError::NotRevocable { id: 1 }.to_string());
+
+    setup.advance_time(300);
+    setup.claim(&user1, 2).unwrap();
+    setup.advance_time(100);
+
+    let revoke = ExecuteMsg::Revoke { id: 2, recipient: Some(treasury.to_string()) };
+    let err = setup.execute(&user1, &revoke).unwrap_err();
+    assert_eq!(err.to_string(), ContractError::Ownership(cw_ownable::OwnershipError::NotOwner).to_string());
+
+    // the beneficiary keeps what vested, the rest goes back
+    setup.execute(&owner, &revoke).unwrap();
+    assert_eq!(setup.balance(&user1), Uint128::from(400u128));
+    assert_eq!(setup.balance(&treasury), Uint128::from(600u128));
+
+    let err = setup.execute(&owner, &revoke).unwrap_err();
+    assert_eq!(err.to_string(), ContractError::AlreadyRevoked { id: 2 }.to_string());
+
+    setup.advance_time(1000);
+    let grant = setup.grant(2);
+    assert_eq!(grant.vested, Uint128::from(400u128));
+    assert_eq!(grant.claimable, Uint128::zero());
+    assert_eq!(grant.revoked_at, Some(start_time + 400));
+    let err = setup.claim(&user1, 2).unwrap_err();
+    assert_eq!(err.to_string(), ContractError::NothingToClaim {}.to_string());
+
+    // the non revocable grant is untouched
+    setup.claim(&user1, 1).unwrap();
+    assert_eq!(setup.balance(&user1), Uint128::from(1400u128));
+}
+
+#[test]
+fn test_grant_queries() {
+    let mut setup = TestSetup::new();
+    let owner = setup.owner.clone();
+    let user1 = setup.addr("user1");
+    let user2 = setup.addr("user2");
+    let start_time = setup.now();
+    let schedule = Schedule::Linear { start_time, end_time: start_time + 1000 };
+
+    for beneficiary in [&user1, &user2, &user1, &user1] {
+        setup.create_grant(&owner, beneficiary, 100, schedule.clone(), false).unwrap();
+    }
+
+    let ids = |res: GrantsResponse| res.grants.iter().map(|g| g.id).collect::<Vec<_>>();
+
+    let res: GrantsResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.vesting_addr, &QueryMsg::Grants { start_after: None, limit: Some(2) })
+        .unwrap();
+    assert_eq!(ids(res), vec![1, 2]);
+
+    let res: GrantsResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.vesting_addr, &QueryMsg::Grants { start_after: Some(2), limit: None })
+        .unwrap();
+    assert_eq!(ids(res), vec![3, 4]);
+
+    let res: GrantsResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.vesting_addr, &QueryMsg::GrantsByBeneficiary {
+            beneficiary: user1.to_string(),
+            start_after: Some(1),
+            limit: None,
+        })
+        .unwrap();
+    assert!(res.grants.iter().all(|g| g.beneficiary == user1.as_str()));
+    assert_eq!(ids(res), vec![3, 4]);
+
+    let res: GrantsResponse = setup.app
+        .wrap()
+        .query_wasm_smart(&setup.vesting_addr, &QueryMsg::GrantsByBeneficiary {
+            beneficiary: user2.to_string(),
+            start_after: None,
+            limit: None,
+        })
+        .unwrap();
+    assert_eq!(ids(res), vec![2]);
+}
\ No newline at end of file
@@ -1,8 +1,20 @@
-use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Uint128;
-
-#[cw_serde]
-pub struct Cw20Coin {
-    pub address: String,
-    pub amount: Uint128,
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Reply, Uint128};
+use cw_utils::{parse_instantiate_response_data, MsgInstantiateContractResponse, ParseReplyError};
+
+#[cw_serde]
+pub struct Cw20Coin {
+    pub address: String,
+    pub amount: Uint128,
+}
+
+/// Reads the instantiate response out of a `reply_on_success` sub message
+pub fn parse_reply_instantiate_data(msg: Reply) -> Result<MsgInstantiateContractResponse, ParseReplyError> {
+    let res = msg.result
+        .into_result()
+        .map_err(ParseReplyError::SubMsgFailure)?;
+    let data = res.msg_responses
+        .first()
+        .ok_or_else(|| ParseReplyError::ParseFailure("Missing reply data".to_string()))?;
+    parse_instantiate_response_data(data.value.as_slice())
 }
\ No newline at end of file
@@ -1,35 +0,0 @@
-use cosmwasm_std::{
-    entry_point, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
-};
-
-use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, QueryMsg, InstantiateMsg};
-
-use cw20_base::contract as base;
-
-#[entry_point]
-pub fn instantiate(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    msg: InstantiateMsg,
-) -> Result<Response, ContractError> {
-    base::instantiate(deps, env, info, msg)
-        .map_err(ContractError::Cw20)
-}
-
-#[entry_point]
-pub fn execute(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    msg: ExecuteMsg,
-) -> Result<Response, ContractError> {
-    base::execute(deps, env, info, msg)
-        .map_err(ContractError::Cw20)
-}
-
-#[entry_point]
-pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    base::query(deps, env, msg)
-}
\ No newline at end of file
@@ -1,4 +0,0 @@
-pub use cw20_base::msg::{InstantiateMsg, ExecuteMsg as Cw20ExecuteMsg, QueryMsg as Cw20QueryMsg};
-
-pub type ExecuteMsg = Cw20ExecuteMsg;
-pub type QueryMsg = Cw20QueryMsg;
\ No newline at end of file
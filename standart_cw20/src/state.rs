@@ -1 +0,0 @@
-pub use cw20_base::state::{TOKEN_INFO, BALANCES, ALLOWANCES, MARKETING_INFO, LOGO};
\ No newline at end of file
@@ -0,0 +1,125 @@
+use cosmwasm_std::{
+    coins, entry_point, to_json_binary, BankMsg, Binary, Decimal, Deps, DepsMut, Env,
+    MessageInfo, Response, StdResult, Uint128,
+};
+use cw_utils::must_pay;
+
+use crate::error::ContractError;
+use crate::msg::{
+    BackingResponse, Cw20ExecuteMsg, ExecuteMsg, InstantiateMsg, QueryMsg, WrapMsg, WrapQueryMsg,
+};
+use crate::state::{BALANCES, DENOM, TOKEN_INFO};
+
+use cw20_base::contract as base;
+
+#[entry_point]
+pub fn instantiate(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    DENOM.save(deps.storage, &msg.denom)?;
+
+    // no minter, so supply only grows through deposits
+    base::instantiate(deps.branch(), env, info, cw20_base::msg::InstantiateMsg {
+        name: msg.name,
+        symbol: msg.symbol,
+        decimals: msg.decimals,
+        initial_balances: vec![],
+        mint: None,
+        marketing: None,
+    })
+    .map_err(ContractError::Cw20)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("denom", msg.denom))
+}
+
+#[entry_point]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        // burning outside of a withdraw would strand the reserve
+        ExecuteMsg::Base(Cw20ExecuteMsg::Burn { .. } | Cw20ExecuteMsg::BurnFrom { .. }) => {
+            Err(ContractError::BurnDisabled {})
+        }
+        ExecuteMsg::Base(msg) => base::execute(deps, env, info, msg)
+            .map_err(ContractError::Cw20),
+        ExecuteMsg::Wrap(WrapMsg::Deposit {}) => execute_deposit(deps, info),
+        ExecuteMsg::Wrap(WrapMsg::Withdraw { amount }) => execute_withdraw(deps, env, info, amount),
+    }
+}
+
+fn execute_deposit(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let denom = DENOM.load(deps.storage)?;
+    let amount = must_pay(&info, &denom)?;
+
+    TOKEN_INFO.update(deps.storage, |mut token_info| -> StdResult<_> {
+        token_info.total_supply = token_info.total_supply.checked_add(amount)?;
+        Ok(token_info)
+    })?;
+    BALANCES.update(deps.storage, &info.sender, |balance| -> StdResult<_> {
+        Ok(balance.unwrap_or_default().checked_add(amount)?)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "deposit")
+        .add_attribute("to", info.sender)
+        .add_attribute("amount", amount))
+}
+
+fn execute_withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let denom = DENOM.load(deps.storage)?;
+    let recipient = info.sender.clone();
+
+    // checks the amount and the balance, and lowers the supply
+    base::execute_burn(deps, env, info, amount)
+        .map_err(ContractError::Cw20)?;
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: coins(amount.u128(), denom),
+        })
+        .add_attribute("action", "withdraw")
+        .add_attribute("from", recipient)
+        .add_attribute("amount", amount))
+}
+
+#[entry_point]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Base(msg) => base::query(deps, env, msg),
+        QueryMsg::Wrap(WrapQueryMsg::Backing {}) => to_json_binary(&query_backing(deps, env)?),
+    }
+}
+
+fn query_backing(deps: Deps, env: Env) -> StdResult<BackingResponse> {
+    let denom = DENOM.load(deps.storage)?;
+    let reserve = deps.querier.query_balance(env.contract.address, &denom)?.amount;
+    let total_supply = TOKEN_INFO.load(deps.storage)?.total_supply;
+
+    let ratio = if total_supply.is_zero() {
+        Decimal::one()
+    } else {
+        Decimal::from_ratio(reserve, total_supply)
+    };
+
+    Ok(BackingResponse {
+        denom,
+        reserve,
+        total_supply,
+        ratio,
+    })
+}
\ No newline at end of file
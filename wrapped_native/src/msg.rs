@@ -0,0 +1,57 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+pub use cw20_base::msg::{ExecuteMsg as Cw20ExecuteMsg, QueryMsg as Cw20QueryMsg};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub name: String,
+    pub symbol: String,
+    /// Should match the native denom's exponent so amounts read the same
+    pub decimals: u8,
+    /// Native denom being wrapped, e.g. `uaxm`
+    pub denom: String,
+}
+
+/// The cw20 messages plus deposit and withdraw, both sent in their plain
+/// json form. Minting happens only through deposits
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum ExecuteMsg {
+    Base(Cw20ExecuteMsg),
+    Wrap(WrapMsg),
+}
+
+#[cw_serde]
+pub enum WrapMsg {
+    /// Mints the sent amount of the native denom to the sender
+    Deposit {},
+    /// Burns `amount` of the sender's tokens and sends back the same
+    /// amount of the native denom
+    Withdraw { amount: Uint128 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum QueryMsg {
+    Base(Cw20QueryMsg),
+    Wrap(WrapQueryMsg),
+}
+
+#[cw_serde]
+pub enum WrapQueryMsg {
+    /// Returns BackingResponse
+    Backing {},
+}
+
+#[cw_serde]
+pub struct BackingResponse {
+    pub denom: String,
+    /// Native balance held by the contract
+    pub reserve: Uint128,
+    pub total_supply: Uint128,
+    /// reserve / total_supply, one while nothing is wrapped
+    pub ratio: Decimal,
+}
\ No newline at end of file
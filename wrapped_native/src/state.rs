@@ -0,0 +1,6 @@
+use cw_storage_plus::Item;
+
+pub use cw20_base::state::{TOKEN_INFO, BALANCES, ALLOWANCES};
+
+// the native denom held in reserve, one cw20 unit per unit deposited
+pub const DENOM: Item<String> = Item::new("denom");
\ No newline at end of file
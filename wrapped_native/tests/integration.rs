@@ -0,0 +1,162 @@
+use cosmwasm_std::{coin, coins, Addr, Decimal, Empty, Uint128};
+use cw20::BalanceResponse;
+use cw_multi_test::{App, AppBuilder, ContractWrapper, Executor};
+use cw_utils::PaymentError;
+
+use wrapped_native::ContractError;
+use wrapped_native::msg::{
+    BackingResponse, Cw20ExecuteMsg, Cw20QueryMsg, ExecuteMsg, InstantiateMsg, QueryMsg, WrapMsg,
+    WrapQueryMsg,
+};
+
+const DENOM: &str = "uaxm";
+
+fn wrapped_contract() -> Box<dyn cw_multi_test::Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        wrapped_native::contract::execute,
+        wrapped_native::contract::instantiate,
+        wrapped_native::contract::query,
+    );
+    Box::new(contract)
+}
+
+struct TestSetup {
+    app: App,
+    token_addr: Addr,
+}
+
+impl TestSetup {
+    fn new() -> Self {
+        let mut app = AppBuilder::new().build(|router, api, storage| {
+            for name in ["user1", "user2"] {
+                router
+                    .bank
+                    .init_balance(storage, &api.addr_make(name), vec![coin(1000, DENOM), coin(1000, "uother")])
+                    .unwrap();
+            }
+        });
+        let owner = app.api().addr_make("owner");
+
+        let code_id = app.store_code(wrapped_contract());
+        let token_addr = app
+            .instantiate_contract(
+                code_id,
+                owner,
+                &InstantiateMsg {
+                    name: "Wrapped AXM".to_string(),
+                    symbol: "WAXM".to_string(),
+                    decimals: 6,
+                    denom: DENOM.to_string(),
+                },
+                &[],
+                "Wrapped AXM",
+                None,
+            )
+            .unwrap();
+
+        TestSetup { app, token_addr }
+    }
+
+    fn addr(&self, name: &str) -> Addr {
+        self.app.api().addr_make(name)
+    }
+
+    fn execute(&mut self, sender: &Addr, msg: &ExecuteMsg, funds: &[cosmwasm_std::Coin]) -> Result<(), ContractError> {
+        self.app
+            .execute_contract(sender.clone(), self.token_addr.clone(), msg, funds)
+            .map(|_| ())
+            .map_err(|err| err.downcast().unwrap())
+    }
+
+    fn balance(&self, address: &Addr) -> Uint128 {
+        let res: BalanceResponse = self.app
+            .wrap()
+            .query_wasm_smart(&self.token_addr, &QueryMsg::Base(Cw20QueryMsg::Balance {
+                address: address.to_string(),
+            }))
+            .unwrap();
+        res.balance
+    }
+
+    fn native_balance(&self, address: &Addr) -> Uint128 {
+        self.app.wrap().query_balance(address, DENOM).unwrap().amount
+    }
+
+    fn backing(&self) -> BackingResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.token_addr, &QueryMsg::Wrap(WrapQueryMsg::Backing {}))
+            .unwrap()
+    }
+}
+
+#[test]
+fn test_deposit_validates_funds() {
+    let mut setup = TestSetup::new();
+    let user1 = setup.addr("user1");
+    let deposit = ExecuteMsg::Wrap(WrapMsg::Deposit {});
+
+    let err = setup.execute(&user1, &deposit, &[]).unwrap_err();
+    assert_eq!(err.to_string(), ContractError::Payment(PaymentError::NoFunds {}).to_string());
+
+    let err = setup.execute(&user1, &deposit, &coins(100, "uother")).unwrap_err();
+    assert_eq!(err.to_string(), ContractError::Payment(PaymentError::MissingDenom(DENOM.to_string())).to_string());
+
+    let err = setup.execute(&user1, &deposit, &[coin(100, DENOM), coin(100, "uother")]).unwrap_err();
+    assert_eq!(err.to_string(), ContractError::Payment(PaymentError::MultipleDenoms {}).to_string());
+
+    setup.execute(&user1, &deposit, &coins(400, DENOM)).unwrap();
+    assert_eq!(setup.balance(&user1), Uint128::from(400u128));
+    assert_eq!(setup.native_balance(&user1), Uint128::from(600u128));
+}
+
+#[test]
+fn test_wrap_transfer_and_withdraw() {
+    let mut setup = TestSetup::new();
+    let user1 = setup.addr("user1");
+    let user2 = setup.addr("user2");
+
+    setup.execute(&user1, &ExecuteMsg::Wrap(WrapMsg::Deposit {}), &coins(500, DENOM)).unwrap();
+    setup.execute(&user1, &ExecuteMsg::Base(Cw20ExecuteMsg::Transfer {
+        recipient: user2.to_string(),
+        amount: Uint128::from(200u128),
+    }), &[]).unwrap();
+
+    let backing = setup.backing();
+    assert_eq!(backing.denom, DENOM);
+    assert_eq!(backing.reserve, Uint128::from(500u128));
+    assert_eq!(backing.total_supply, Uint128::from(500u128));
+    assert_eq!(backing.ratio, Decimal::one());
+
+    let err = setup.execute(&user2, &ExecuteMsg::Wrap(WrapMsg::Withdraw { amount: Uint128::from(201u128) }), &[]).unwrap_err();
+    assert!(matches!(err, ContractError::Cw20(_)));
+
+    // the wrapped tokens come back out as the native denom
+    setup.execute(&user2, &ExecuteMsg::Wrap(WrapMsg::Withdraw { amount: Uint128::from(200u128) }), &[]).unwrap();
+    assert_eq!(setup.balance(&user2), Uint128::zero());
+    assert_eq!(setup.native_balance(&user2), Uint128::from(1200u128));
+
+    let backing = setup.backing();
+    assert_eq!(backing.reserve, Uint128::from(300u128));
+    assert_eq!(backing.total_supply, Uint128::from(300u128));
+    assert_eq!(backing.ratio, Decimal::one());
+}
+
+#[test]
+fn test_supply_only_changes_through_the_reserve() {
+    let mut setup = TestSetup::new();
+    let owner = setup.addr("owner");
+    let user1 = setup.addr("user1");
+    setup.execute(&user1, &ExecuteMsg::Wrap(WrapMsg::Deposit {}), &coins(100, DENOM)).unwrap();
+
+    let err = setup.execute(&user1, &ExecuteMsg::Base(Cw20ExecuteMsg::Burn { amount: Uint128::one() }), &[]).unwrap_err();
+    assert_eq!(err.to_string(), ContractError::BurnDisabled {}.to_string());
+
+    let err = setup.execute(&owner, &ExecuteMsg::Base(Cw20ExecuteMsg::Mint {
+        recipient: owner.to_string(),
+        amount: Uint128::one(),
+    }), &[]).unwrap_err();
+    assert_eq!(err.to_string(), ContractError::Cw20(cw20_base::ContractError::Unauthorized {}).to_string());
+
+    assert_eq!(setup.backing().total_supply, Uint128::from(100u128));
+}
\ No newline at end of file